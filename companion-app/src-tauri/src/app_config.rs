@@ -11,23 +11,109 @@ pub struct CompanionConfig {
     pub onboarding_complete: bool,
     /// If true, user opted in to sending ranked duo match metadata for site stats (future use).
     pub share_ranked_duo_stats: bool,
+    /// Game minutes at which live CS is recorded for the checklist (empty = 5 and 10).
+    #[serde(default)]
+    pub cs_milestone_minutes: Vec<u32>,
+    /// What to do with remakes, early surrenders and non-Rift modes after a game.
+    #[serde(default)]
+    pub game_scoring: GameScoringSettings,
+    /// Days raw game archives are kept (`None` = forever).
+    #[serde(default)]
+    pub archive_retention_days: Option<u32>,
+    /// Play session grouping and tilt warnings.
+    #[serde(default)]
+    pub sessions: SessionSettings,
 }
 
-fn config_dir() -> PathBuf {
-    #[cfg(target_os = "windows")]
-    {
-        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".into());
-        PathBuf::from(appdata).join("Lelanation").join("Companion")
+/// Ordered from least to most restrictive.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum ScoringAction {
+    /// Evaluate with the mode's profile.
+    Evaluate,
+    /// Save the game but leave it out of the score.
+    Unscored,
+    /// Do not save the game.
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameScoringSettings {
+    pub remake: ScoringAction,
+    pub early_surrender: ScoringAction,
+    pub leaver: ScoringAction,
+    pub aram: ScoringAction,
+    pub arena: ScoringAction,
+    pub other_modes: ScoringAction,
+}
+
+impl Default for GameScoringSettings {
+    fn default() -> Self {
+        Self {
+            remake: ScoringAction::Skip,
+            early_surrender: ScoringAction::Evaluate,
+            leaver: ScoringAction::Unscored,
+            aram: ScoringAction::Evaluate,
+            arena: ScoringAction::Unscored,
+            other_modes: ScoringAction::Unscored,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionSettings {
+    /// Minutes without a game after which the next game starts a new session.
+    pub gap_minutes: u32,
+    pub tilt_warnings: bool,
+    /// Losses in a row that count as a tilt signal.
+    pub loss_streak: u32,
+    /// Score points lost between the first and second half of the session.
+    pub score_drop: f32,
+    /// Extra deaths per game between the first and second half of the session.
+    pub deaths_rise: f32,
+    /// Signals at which the warning recommends to stop playing (0 = never).
+    pub stop_after_signals: u32,
+}
+
+impl Default for SessionSettings {
+    fn default() -> Self {
+        Self {
+            gap_minutes: 45,
+            tilt_warnings: true,
+            loss_streak: 3,
+            score_drop: 15.0,
+            deaths_rise: 2.0,
+            stop_after_signals: 2,
+        }
     }
-    #[cfg(not(target_os = "windows"))]
-    {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
-        PathBuf::from(home)
-            .join(".config")
-            .join("lelanation-companion")
+}
+
+const DEFAULT_CS_MILESTONE_MINUTES: &[u32] = &[5, 10];
+
+impl CompanionConfig {
+    /// Sorted, de-duplicated CS milestones, falling back to the defaults.
+    pub fn cs_milestone_minutes(&self) -> Vec<u32> {
+        let mut minutes: Vec<u32> = self
+            .cs_milestone_minutes
+            .iter()
+            .copied()
+            .filter(|&m| m > 0)
+            .collect();
+        if minutes.is_empty() {
+            return DEFAULT_CS_MILESTONE_MINUTES.to_vec();
+        }
+        minutes.sort_unstable();
+        minutes.dedup();
+        minutes
     }
 }
 
+fn config_dir() -> PathBuf {
+    crate::db::data_dir()
+}
+
 pub fn config_path() -> PathBuf {
     config_dir().join("config.json")
 }
@@ -0,0 +1,264 @@
+//! Trends over the checklist history: rolling averages, breakdowns and item pass rates.
+
+use super::evaluator::{effective_kind, is_measurable, CheckResultKind, SavedChecklist};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+pub const DEFAULT_WINDOWS: &[u32] = &[10, 20, 50];
+
+/// Averages over a set of games; `lp_change` only counts games where it is known.
+#[derive(Debug, Clone, Serialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricSummary {
+    pub games: u32,
+    pub avg_score: f32,
+    pub avg_cs_per_min: f32,
+    pub avg_deaths: f32,
+    pub avg_wards_per_min: f32,
+    pub win_rate: f32,
+    pub lp_change_total: i32,
+    pub avg_lp_change: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingPoint {
+    pub saved_at_ms: i64,
+    pub game_id: Option<u64>,
+    /// This game's score, next to the averages of the window ending on it.
+    pub score: f32,
+    pub avg_score: f32,
+    pub avg_cs_per_min: f32,
+    pub avg_deaths: f32,
+    pub avg_wards_per_min: f32,
+    pub win_rate: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollingSeries {
+    pub window: u32,
+    /// Averages of the last `window` games.
+    pub latest: MetricSummary,
+    /// Oldest first.
+    pub points: Vec<RollingPoint>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupSummary {
+    /// Champion id, or queue type (`RANKED_SOLO_5x5`, …; queue id when unknown).
+    pub key: String,
+    #[serde(flatten)]
+    pub summary: MetricSummary,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemPassRate {
+    pub id: String,
+    pub measured: u32,
+    pub checked: u32,
+    pub partial: u32,
+    pub failed: u32,
+    /// Checked share of measured games (0–1).
+    pub pass_rate: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistAnalytics {
+    pub overall: MetricSummary,
+    pub rolling: Vec<RollingSeries>,
+    pub by_champion: Vec<GroupSummary>,
+    pub by_queue: Vec<GroupSummary>,
+    pub items: Vec<ItemPassRate>,
+}
+
+fn per_min(value: u32, seconds: u32) -> f32 {
+    value as f32 / (seconds.max(1) as f32 / 60.0)
+}
+
+fn summarize(games: &[&SavedChecklist]) -> MetricSummary {
+    if games.is_empty() {
+        return MetricSummary::default();
+    }
+    let n = games.len() as f32;
+    let avg = |f: &dyn Fn(&SavedChecklist) -> f32| games.iter().map(|g| f(g)).sum::<f32>() / n;
+    let lp_changes: Vec<i32> = games.iter().filter_map(|g| g.stats.lp_change).collect();
+    let lp_change_total = lp_changes.iter().sum();
+    MetricSummary {
+        games: games.len() as u32,
+        avg_score: avg(&|g| g.score),
+        avg_cs_per_min: avg(&|g| per_min(g.stats.cs_total, g.stats.game_duration_seconds)),
+        avg_deaths: avg(&|g| g.stats.deaths as f32),
+        avg_wards_per_min: avg(&|g| per_min(g.stats.wards_placed, g.stats.game_duration_seconds)),
+        win_rate: avg(&|g| if g.stats.win { 1.0 } else { 0.0 }),
+        lp_change_total,
+        avg_lp_change: (!lp_changes.is_empty())
+            .then(|| lp_change_total as f32 / lp_changes.len() as f32),
+    }
+}
+
+fn rolling(games: &[&SavedChecklist], window: u32) -> RollingSeries {
+    let window_len = window.max(1) as usize;
+    let points = (0..games.len())
+        .map(|i| {
+            let start = (i + 1).saturating_sub(window_len);
+            let summary = summarize(&games[start..=i]);
+            RollingPoint {
+                saved_at_ms: games[i].saved_at_ms,
+                game_id: games[i].stats.game_id,
+                score: games[i].score,
+                avg_score: summary.avg_score,
+                avg_cs_per_min: summary.avg_cs_per_min,
+                avg_deaths: summary.avg_deaths,
+                avg_wards_per_min: summary.avg_wards_per_min,
+                win_rate: summary.win_rate,
+            }
+        })
+        .collect();
+    let start = games.len().saturating_sub(window_len);
+    RollingSeries {
+        window,
+        latest: summarize(&games[start..]),
+        points,
+    }
+}
+
+/// Groups sorted by game count (most played first).
+fn group_by(
+    games: &[&SavedChecklist],
+    key: impl Fn(&SavedChecklist) -> String,
+) -> Vec<GroupSummary> {
+    let mut groups: BTreeMap<String, Vec<&SavedChecklist>> = BTreeMap::new();
+    for game in games {
+        groups.entry(key(game)).or_default().push(game);
+    }
+    let mut out: Vec<GroupSummary> = groups
+        .into_iter()
+        .map(|(key, games)| GroupSummary {
+            key,
+            summary: summarize(&games),
+        })
+        .collect();
+    out.sort_by_key(|g| std::cmp::Reverse(g.summary.games));
+    out
+}
+
+fn queue_key(entry: &SavedChecklist) -> String {
+    match (&entry.stats.queue_type, entry.stats.queue_id) {
+        (queue_type, _) if !queue_type.is_empty() => queue_type.clone(),
+        (_, Some(id)) => id.to_string(),
+        _ => "unknown".into(),
+    }
+}
+
+pub(crate) fn item_pass_rates(games: &[&SavedChecklist]) -> Vec<ItemPassRate> {
+    let mut rates: Vec<ItemPassRate> = Vec::new();
+    for (game, item) in games
+        .iter()
+        .flat_map(|g| g.items.iter().map(move |item| (g, item)))
+    {
+        if !is_measurable(&item.id) {
+            continue;
+        }
+        let kind = effective_kind(item, game.unscored);
+        if kind == CheckResultKind::Unmeasurable {
+            continue;
+        }
+        let idx = match rates.iter().position(|r| r.id == item.id) {
+            Some(idx) => idx,
+            None => {
+                rates.push(ItemPassRate {
+                    id: item.id.clone(),
+                    measured: 0,
+                    checked: 0,
+                    partial: 0,
+                    failed: 0,
+                    pass_rate: 0.0,
+                });
+                rates.len() - 1
+            }
+        };
+        let rate = &mut rates[idx];
+        rate.measured += 1;
+        match kind {
+            CheckResultKind::Checked => rate.checked += 1,
+            CheckResultKind::Partial => rate.partial += 1,
+            _ => rate.failed += 1,
+        }
+        rate.pass_rate = rate.checked as f32 / rate.measured as f32;
+    }
+    rates
+}
+
+/// Analytics over scored games (unscored remakes and modes are left out).
+pub fn analyze(history: &[SavedChecklist], windows: &[u32]) -> ChecklistAnalytics {
+    let mut games: Vec<&SavedChecklist> = history.iter().filter(|e| !e.unscored).collect();
+    games.sort_by_key(|e| e.saved_at_ms);
+    ChecklistAnalytics {
+        overall: summarize(&games),
+        rolling: windows.iter().map(|&w| rolling(&games, w)).collect(),
+        by_champion: group_by(&games, |e| e.stats.champion_id.to_string()),
+        by_queue: group_by(&games, queue_key),
+        items: item_pass_rates(&games),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+    use crate::postgame::PostGameStats;
+
+    fn game(saved_at_ms: i64, champion_id: u32, deaths: u32, win: bool) -> SavedChecklist {
+        let stats = PostGameStats {
+            game_duration_seconds: 1800,
+            cs_total: 210,
+            wards_placed: 15,
+            champion_id,
+            deaths,
+            win,
+            queue_type: "RANKED_SOLO_5x5".into(),
+            lp_change: Some(if win { 20 } else { -18 }),
+            ..Default::default()
+        };
+        let mut entry = test_checklist(stats);
+        entry.saved_at_ms = saved_at_ms;
+        entry
+    }
+
+    #[test]
+    fn aggregates_windows_groups_and_items() {
+        let mut history = vec![
+            game(1, 22, 1, true),
+            game(2, 22, 6, false),
+            game(3, 51, 2, true),
+        ];
+        let mut remake = game(4, 51, 0, false);
+        remake.unscored = true;
+        history.push(remake);
+
+        let analytics = analyze(&history, &[2]);
+        assert_eq!(analytics.overall.games, 3);
+        assert_eq!(analytics.overall.avg_cs_per_min, 7.0);
+        assert_eq!(analytics.overall.avg_deaths, 3.0);
+        assert_eq!(analytics.overall.lp_change_total, 22);
+
+        let series = &analytics.rolling[0];
+        assert_eq!(series.points.len(), 3);
+        assert_eq!(series.points[2].avg_deaths, 4.0);
+        assert_eq!(series.latest.win_rate, 0.5);
+
+        assert_eq!(analytics.by_champion[0].key, "22");
+        assert_eq!(analytics.by_champion[0].summary.games, 2);
+        assert_eq!(analytics.by_queue.len(), 1);
+
+        let survival = analytics.items.iter().find(|i| i.id == "survival").unwrap();
+        assert_eq!(
+            (survival.measured, survival.checked, survival.failed),
+            (3, 2, 1)
+        );
+    }
+}
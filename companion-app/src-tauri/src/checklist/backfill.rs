@@ -0,0 +1,143 @@
+//! Backfill checklist history from LCU match history.
+
+use super::checklist_for_game;
+use super::rules::load_rules;
+use super::store::{insert_missing, load_all};
+use crate::app_config::load_companion_config;
+use crate::lcu::LcuClient;
+use crate::postgame::{self, archive, game_timeline, match_history};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::thread;
+use tauri::{AppHandle, Emitter};
+
+const PAGE_SIZE: u32 = 20;
+pub const DEFAULT_MAX_GAMES: u32 = 100;
+const MAX_GAMES_LIMIT: u32 = 500;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackfillProgress {
+    pub scanned: u32,
+    pub max_games: u32,
+    pub added: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    /// Games whose raw payload could not be written to the archive.
+    pub archive_failed: u32,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+pub fn start(app: AppHandle, max_games: Option<u32>, max_age_days: Option<u32>) {
+    let max_games = max_games
+        .unwrap_or(DEFAULT_MAX_GAMES)
+        .clamp(1, MAX_GAMES_LIMIT);
+    thread::spawn(move || {
+        let mut progress = BackfillProgress {
+            scanned: 0,
+            max_games,
+            added: 0,
+            skipped: 0,
+            failed: 0,
+            archive_failed: 0,
+            done: false,
+            error: None,
+        };
+        if let Err(e) = run(&app, max_age_days, &mut progress) {
+            progress.error = Some(e);
+        }
+        progress.done = true;
+        let _ = app.emit("checklist:backfill-progress", &progress);
+    });
+}
+
+fn run(
+    app: &AppHandle,
+    max_age_days: Option<u32>,
+    progress: &mut BackfillProgress,
+) -> Result<(), String> {
+    let client = LcuClient::connect()?;
+    if !client.is_connected() {
+        return Err("League Client is not reachable".into());
+    }
+    let summoner_id = postgame::local_summoner_id(&client)
+        .ok_or_else(|| "Cannot resolve summoner id".to_string())?;
+    let oldest_ms = max_age_days
+        .map(|days| super::evaluator::now_millis() - i64::from(days) * 24 * 3600 * 1000);
+    let known: HashSet<u64> = load_all()?.iter().filter_map(|e| e.stats.game_id).collect();
+    let config = load_companion_config();
+    let rules = load_rules();
+    let minutes = config.cs_milestone_minutes();
+
+    let mut beg = 0;
+    let mut reached_cutoff = false;
+    while progress.scanned < progress.max_games && !reached_cutoff {
+        let end = (beg + PAGE_SIZE).min(progress.max_games);
+        let page = match_history::fetch_match_page(&client, beg, end)?;
+        let mut batch = Vec::new();
+        for summary in &page {
+            if progress.scanned >= progress.max_games {
+                break;
+            }
+            if oldest_ms.is_some_and(|oldest| summary.game_creation_ms < oldest) {
+                reached_cutoff = true;
+                break;
+            }
+            progress.scanned += 1;
+            if known.contains(&summary.game_id) {
+                progress.skipped += 1;
+                continue;
+            }
+            let parsed =
+                match_history::fetch_match_game(&client, summary.game_id).and_then(|game| {
+                    let mut stats = match_history::parse_match_game(&game, summoner_id)?;
+                    if let Some(participant_id) =
+                        match_history::local_participant_id(&game, summoner_id)
+                    {
+                        if let Ok(frames) = game_timeline::fetch_game_timeline(
+                            &client,
+                            summary.game_id,
+                            participant_id,
+                        ) {
+                            game_timeline::fill_missing_milestones(&mut stats, &frames, &minutes);
+                            stats.minute_frames = frames;
+                        }
+                    }
+                    let ended_at_ms = match_history::game_end_ms(&game);
+                    let archived =
+                        archive::store(summary.game_id, summoner_id, None, Some(game)).is_ok();
+                    Ok((stats, ended_at_ms, archived))
+                });
+            match parsed {
+                Ok((stats, ended_at_ms, archived)) => {
+                    if !archived {
+                        progress.archive_failed += 1;
+                    }
+                    match checklist_for_game(stats, true, &config.game_scoring, &rules) {
+                        Some(mut entry) => {
+                            entry.saved_at_ms = ended_at_ms;
+                            entry.id = format!("cl-{ended_at_ms}");
+                            batch.push(entry);
+                        }
+                        None => progress.skipped += 1,
+                    }
+                }
+                Err(_) => progress.failed += 1,
+            }
+        }
+        let batch_len = batch.len() as u32;
+        let (history, added) = insert_missing(batch)?;
+        progress.added += added;
+        progress.skipped += batch_len - added;
+        let _ = app.emit("checklist:backfill-progress", &*progress);
+        if added > 0 {
+            let _ = app.emit("lcu:checklist-history", &history);
+        }
+        if (page.len() as u32) < end - beg {
+            break;
+        }
+        beg = end;
+    }
+    Ok(())
+}
@@ -1,5 +1,11 @@
 //! Post-game checklist scoring (simple LCU/Live Client stats).
 
+use super::expr::{self, Variables};
+use super::rules::{load_rules, ChecklistRules, CustomItem, ScoreWeights, Threshold, Thresholds};
+use crate::app_config::{load_companion_config, GameScoringSettings, ScoringAction};
+use crate::live_client::TimelineEvent;
+use crate::postgame::game_kind::{classify, GameClass, GameEnding, GameMode};
+use crate::postgame::role::{detect_role, Role};
 use crate::postgame::PostGameStats;
 use serde::{Deserialize, Serialize};
 
@@ -22,6 +28,9 @@ pub struct ChecklistItem {
     pub user_kind: Option<CheckResultKind>,
     #[serde(default)]
     pub manual_checked: bool,
+    /// Display name of custom items (built-in items are translated by id).
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,8 +46,34 @@ pub struct SavedChecklist {
     pub stats: PostGameStats,
     #[serde(default)]
     pub notes: String,
+    /// Kills, deaths and objectives from the Live Client event feed.
+    #[serde(default)]
+    pub timeline: Vec<TimelineEvent>,
+    #[serde(default)]
+    pub game_class: GameClass,
+    /// Saved for the record but left out of the score (remake, leaver, …).
+    #[serde(default)]
+    pub unscored: bool,
+    /// `version` of the checklist rules the items were evaluated with.
+    #[serde(default)]
+    pub ruleset_version: String,
+    /// Weights `score` was computed with (equal weights, half credit for older entries).
+    #[serde(default)]
+    pub weights: ScoreWeights,
+    /// User labels for filtering the history (`tilt`, `duo`, …).
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Items suggested as pre-game focus for this game (see `focus::suggest`).
+    #[serde(default)]
+    pub focus: Vec<String>,
+    /// `EVALUATOR_VERSION` the items were evaluated with (0: saved before it existed).
+    #[serde(default)]
+    pub evaluator_version: u32,
 }
 
+/// Bump when the built-in evaluation changes (items, formulas, role or mode handling).
+pub const EVALUATOR_VERSION: u32 = 1;
+
 const MEASURABLE: &[&str] = &[
     "cs_at_5",
     "cs_at_10",
@@ -48,6 +83,8 @@ const MEASURABLE: &[&str] = &[
     "vision_denied",
     "survival",
     "team_objectives",
+    "vision_score",
+    "objective_participation",
 ];
 
 fn item(id: &str, kind: CheckResultKind, detail: Option<&str>) -> ChecklistItem {
@@ -57,6 +94,7 @@ fn item(id: &str, kind: CheckResultKind, detail: Option<&str>) -> ChecklistItem
         detail: detail.map(str::to_string),
         user_kind: None,
         manual_checked: false,
+        label: None,
     }
 }
 
@@ -76,12 +114,37 @@ fn unmeasurable(id: &str) -> ChecklistItem {
     item(id, CheckResultKind::Unmeasurable, None)
 }
 
+fn not_applicable(id: &str) -> ChecklistItem {
+    item(id, CheckResultKind::Unmeasurable, Some("not_applicable"))
+}
+
+/// Items that only make sense on Summoner's Rift.
+const RIFT_ONLY: &[&str] = &[
+    "cs_at_5",
+    "cs_at_10",
+    "cs_per_min",
+    "pink_wards",
+    "wards_placed",
+    "vision_denied",
+    "team_objectives",
+    "vision_score",
+    "objective_participation",
+];
+
+/// Id prefix of items defined in the rules' `customItems`.
+pub const CUSTOM_PREFIX: &str = "custom:";
+
 pub fn is_measurable(id: &str) -> bool {
-    MEASURABLE.contains(&id)
+    MEASURABLE.contains(&id) || id.starts_with(CUSTOM_PREFIX)
 }
 
-pub fn effective_kind(entry: &ChecklistItem) -> CheckResultKind {
-    if is_measurable(&entry.id) {
+/// `detail` of the items of an unscored entry; user overrides do not bring them back.
+/// Items of unscored entries (`SavedChecklist::unscored`) stay out of the score whatever the
+/// user set.
+pub fn effective_kind(entry: &ChecklistItem, unscored: bool) -> CheckResultKind {
+    if is_measurable(&entry.id) && unscored {
+        CheckResultKind::Unmeasurable
+    } else if is_measurable(&entry.id) {
         entry.user_kind.clone().unwrap_or(entry.kind.clone())
     } else if entry.manual_checked {
         CheckResultKind::Checked
@@ -90,126 +153,195 @@ pub fn effective_kind(entry: &ChecklistItem) -> CheckResultKind {
     }
 }
 
-pub fn recalculate_score(items: &[ChecklistItem]) -> (f32, u32, u32) {
-    let measured: Vec<_> = items.iter().filter(|i| is_measurable(&i.id)).collect();
+/// Weighted share of measured items (partial earns `partial_credit`), with counts.
+pub fn recalculate_score(
+    items: &[ChecklistItem],
+    weights: &ScoreWeights,
+    unscored: bool,
+) -> (f32, u32, u32) {
+    let measured: Vec<_> = items
+        .iter()
+        .filter(|i| {
+            is_measurable(&i.id) && effective_kind(i, unscored) != CheckResultKind::Unmeasurable
+        })
+        .collect();
     let measured_count = measured.len() as u32;
     let checked_count = measured
         .iter()
-        .filter(|i| effective_kind(i) == CheckResultKind::Checked)
+        .filter(|i| effective_kind(i, unscored) == CheckResultKind::Checked)
         .count() as u32;
-    let partial_count = measured
+    let total_weight: f32 = measured.iter().map(|i| weights.weight(&i.id)).sum();
+    let earned: f32 = measured
         .iter()
-        .filter(|i| effective_kind(i) == CheckResultKind::Partial)
-        .count() as u32;
-    let score = if measured_count == 0 {
+        .map(|i| {
+            let credit = match effective_kind(i, unscored) {
+                CheckResultKind::Checked => 1.0,
+                CheckResultKind::Partial => weights.partial_credit,
+                _ => 0.0,
+            };
+            weights.weight(&i.id) * credit
+        })
+        .sum();
+    let score = if total_weight <= 0.0 {
         0.0
     } else {
-        ((checked_count as f32 + partial_count as f32 * 0.5) / measured_count as f32) * 100.0
+        (earned / total_weight) * 100.0
     };
     (score, measured_count, checked_count)
 }
 
-fn eval_cs_at_5(cs: Option<u32>) -> ChecklistItem {
-    let Some(cs) = cs else {
-        return partial("cs_at_5", "live_client_unavailable");
-    };
-    if cs >= 44 {
-        return checked("cs_at_5", Some(&format!("{cs}")));
-    }
-    if cs >= 42 {
-        return checked("cs_at_5", Some(&format!("{cs}")));
-    }
-    if cs >= 40 {
-        return checked("cs_at_5", Some(&format!("{cs}")));
-    }
-    if cs >= 35 {
-        return partial("cs_at_5", &format!("{cs}"));
-    }
-    failed("cs_at_5", &format!("{cs}"))
+fn minutes(stats: &PostGameStats) -> f32 {
+    stats.game_duration_seconds.max(1) as f32 / 60.0
 }
 
-fn eval_cs_at_10(cs: Option<u32>) -> ChecklistItem {
-    let Some(cs) = cs else {
-        return partial("cs_at_10", "live_client_unavailable");
-    };
-    if cs >= 85 {
-        return checked("cs_at_10", Some(&format!("{cs}")));
+fn eval_survival(deaths: u32, t: Threshold) -> ChecklistItem {
+    let deaths_f = deaths as f32;
+    if deaths_f <= t.checked {
+        return checked("survival", Some(&deaths.to_string()));
     }
-    if cs >= 78 {
-        return partial("cs_at_10", &format!("{cs}"));
+    if deaths_f <= t.partial {
+        return partial("survival", &deaths.to_string());
     }
-    failed("cs_at_10", &format!("{cs}"))
+    failed("survival", &deaths.to_string())
 }
 
-fn eval_cs_per_min(stats: &PostGameStats) -> ChecklistItem {
-    let mins = stats.game_duration_seconds.max(1) as f32 / 60.0;
-    let cs_per_min = stats.cs_total as f32 / mins;
-    if cs_per_min >= 8.0 {
-        return checked("cs_per_min", Some(&format!("{cs_per_min:.1}")));
+/// Higher is better: `t.checked` and above is checked, `t.partial` and above partial.
+fn eval_at_least(id: &str, value: f32, t: Threshold, detail: &str) -> ChecklistItem {
+    if value >= t.checked {
+        return checked(id, Some(detail));
     }
-    if cs_per_min >= 6.5 {
-        return partial("cs_per_min", &format!("{cs_per_min:.1}"));
+    if value >= t.partial {
+        return partial(id, detail);
     }
-    failed("cs_per_min", &format!("{cs_per_min:.1}"))
+    failed(id, detail)
 }
 
-pub fn evaluate(stats: PostGameStats) -> Vec<ChecklistItem> {
-    let mut items = Vec::new();
-    items.push(eval_cs_at_5(stats.cs_at_5));
-    items.push(eval_cs_at_10(stats.cs_at_10));
-    items.push(eval_cs_per_min(&stats));
-
-    if stats.vision_wards_bought >= 2 {
-        items.push(checked(
-            "pink_wards",
-            Some(&stats.vision_wards_bought.to_string()),
-        ));
-    } else if stats.vision_wards_bought >= 1 {
-        items.push(checked("pink_wards", Some("1")));
-    } else {
-        items.push(failed("pink_wards", "0"));
+fn eval_cs_milestone(id: &str, cs: Option<u32>, t: Threshold) -> ChecklistItem {
+    match cs {
+        Some(cs) => eval_at_least(id, cs as f32, t, &cs.to_string()),
+        None => partial(id, "live_client_unavailable"),
     }
+}
 
-    let mins = stats.game_duration_seconds.max(1) as f32 / 60.0;
-    let wards_per_min = stats.wards_placed as f32 / mins;
-    if wards_per_min >= 1.0 {
-        items.push(checked("wards_placed", Some(&format!("{wards_per_min:.1}"))));
-    } else if wards_per_min >= 0.5 {
-        items.push(partial("wards_placed", &format!("{wards_per_min:.1}")));
-    } else {
-        items.push(failed("wards_placed", &format!("{wards_per_min:.1}")));
-    }
+fn eval_per_min(id: &str, value: u32, stats: &PostGameStats, t: Threshold) -> ChecklistItem {
+    let per_min = value as f32 / minutes(stats);
+    eval_at_least(id, per_min, t, &format!("{per_min:.1}"))
+}
 
-    if stats.wards_killed >= 1 {
-        items.push(checked(
-            "vision_denied",
-            Some(&stats.wards_killed.to_string()),
-        ));
+/// Junglers: a share of the team's dragons / barons on top of the laner items.
+fn jungle_items(stats: &PostGameStats, t: &Thresholds) -> Vec<ChecklistItem> {
+    let team_objectives = stats.team_dragon_kills + stats.team_baron_kills;
+    let participation = if team_objectives == 0 {
+        partial("objective_participation", "0/0")
     } else {
-        items.push(partial("vision_denied", "0"));
-    }
+        let share = stats.epic_monster_kills as f32 / team_objectives as f32;
+        let detail = format!("{}/{team_objectives}", stats.epic_monster_kills);
+        eval_at_least(
+            "objective_participation",
+            share,
+            t.objective_participation,
+            &detail,
+        )
+    };
+    vec![participation]
+}
 
-    if stats.deaths == 0 {
-        items.push(checked("survival", Some("0")));
-    } else if stats.deaths <= 2 {
-        items.push(checked("survival", Some(&stats.deaths.to_string())));
-    } else if stats.deaths <= 4 {
-        items.push(partial("survival", &stats.deaths.to_string()));
-    } else {
-        items.push(failed("survival", &stats.deaths.to_string()));
-    }
+/// Supports: no CS items, vision score per minute.
+fn support_items(stats: &PostGameStats, t: &Thresholds) -> Vec<ChecklistItem> {
+    vec![
+        not_applicable("cs_at_5"),
+        not_applicable("cs_at_10"),
+        not_applicable("cs_per_min"),
+        eval_per_min(
+            "vision_score",
+            stats.vision_score,
+            stats,
+            t.vision_score_per_min,
+        ),
+    ]
+}
 
-    if stats.team_dragon_kills > 0 || stats.team_baron_kills > 0 {
-        items.push(checked(
-            "team_objectives",
-            Some(&format!(
-                "{}:{}",
-                stats.team_dragon_kills, stats.team_baron_kills
-            )),
-        ));
-    } else {
-        items.push(partial("team_objectives", "0:0"));
+/// Expression result against the item's thresholds; unmeasurable when it has no value.
+fn eval_custom(custom: &CustomItem, vars: &Variables) -> ChecklistItem {
+    let id = format!("{CUSTOM_PREFIX}{}", custom.id);
+    let value = expr::parse(&custom.expression).and_then(|e| expr::eval(&e, vars));
+    let mut entry = match (value, custom.threshold) {
+        (Err(e), _) => item(&id, CheckResultKind::Unmeasurable, Some(&e)),
+        (Ok(v), None) if v != 0.0 => checked(&id, None),
+        (Ok(_), None) => failed(&id, "0"),
+        (Ok(v), Some(t)) => {
+            let detail = if v.fract() == 0.0 {
+                format!("{v}")
+            } else {
+                format!("{v:.1}")
+            };
+            let v = v as f32;
+            let kind = match custom.lower_is_better {
+                false if v >= t.checked => CheckResultKind::Checked,
+                false if v >= t.partial => CheckResultKind::Partial,
+                true if v <= t.checked => CheckResultKind::Checked,
+                true if v <= t.partial => CheckResultKind::Partial,
+                _ => CheckResultKind::Failed,
+            };
+            item(&id, kind, Some(&detail))
+        }
+    };
+    entry.label = Some(custom.label.clone());
+    entry
+}
+
+/// Replace items with the same id, append role-only ones.
+fn apply_overrides(items: &mut Vec<ChecklistItem>, overrides: Vec<ChecklistItem>) {
+    for entry in overrides {
+        match items.iter_mut().find(|i| i.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => items.push(entry),
+        }
     }
+}
+
+fn evaluate_laner(stats: &PostGameStats, t: &Thresholds) -> Vec<ChecklistItem> {
+    let mut items = Vec::new();
+    items.push(eval_cs_milestone("cs_at_5", stats.cs_at(5), t.cs_at_5));
+    items.push(eval_cs_milestone("cs_at_10", stats.cs_at(10), t.cs_at_10));
+    items.push(eval_per_min(
+        "cs_per_min",
+        stats.cs_total,
+        stats,
+        t.cs_per_min,
+    ));
+
+    let pinks = stats.vision_wards_bought;
+    items.push(eval_at_least(
+        "pink_wards",
+        pinks as f32,
+        t.pink_wards,
+        &pinks.to_string(),
+    ));
+    items.push(eval_per_min(
+        "wards_placed",
+        stats.wards_placed,
+        stats,
+        t.wards_per_min,
+    ));
+
+    let denied = stats.wards_killed;
+    items.push(eval_at_least(
+        "vision_denied",
+        denied as f32,
+        t.vision_denied,
+        &denied.to_string(),
+    ));
+
+    items.push(eval_survival(stats.deaths, t.deaths));
+
+    let team_objectives = stats.team_dragon_kills + stats.team_baron_kills;
+    items.push(eval_at_least(
+        "team_objectives",
+        team_objectives as f32,
+        t.team_objectives,
+        &format!("{}:{}", stats.team_dragon_kills, stats.team_baron_kills),
+    ));
 
     items.push(unmeasurable("minimap"));
     items.push(unmeasurable("ping_missing"));
@@ -219,6 +351,82 @@ pub fn evaluate(stats: PostGameStats) -> Vec<ChecklistItem> {
     items
 }
 
+/// Items with thresholds for the mode, role and rank (no lane or vision items off the Rift).
+pub fn evaluate_with(
+    stats: &PostGameStats,
+    mode: GameMode,
+    rules: &ChecklistRules,
+) -> Vec<ChecklistItem> {
+    let role = detect_role(stats);
+    let t = rules.resolve(mode, role, &stats.player_rank);
+    let mut items = evaluate_laner(stats, &t);
+    match role {
+        Role::Jungle => apply_overrides(&mut items, jungle_items(stats, &t)),
+        Role::Support => apply_overrides(&mut items, support_items(stats, &t)),
+        _ => {}
+    }
+    let vars = Variables::new(stats);
+    items.extend(rules.custom_items.iter().map(|c| eval_custom(c, &vars)));
+    if mode == GameMode::SummonersRift {
+        return items;
+    }
+    for entry in &mut items {
+        if RIFT_ONLY.contains(&entry.id.as_str())
+            || (entry.id == "survival" && mode == GameMode::Arena)
+        {
+            *entry = not_applicable(&entry.id);
+        }
+    }
+    items
+}
+
+fn scoring_action(class: GameClass, settings: &GameScoringSettings) -> ScoringAction {
+    let ending = match class.ending {
+        GameEnding::Normal => ScoringAction::Evaluate,
+        GameEnding::Remake => settings.remake,
+        GameEnding::EarlySurrender => settings.early_surrender,
+        GameEnding::Leaver => settings.leaver,
+    };
+    let mode = match class.mode {
+        GameMode::SummonersRift => ScoringAction::Evaluate,
+        GameMode::Aram => settings.aram,
+        GameMode::Arena => settings.arena,
+        GameMode::Other => settings.other_modes,
+    };
+    ending.max(mode)
+}
+
+/// Keep the entry but take every measurable item out of the score.
+fn mark_unscored(entry: &mut SavedChecklist) {
+    entry.unscored = true;
+    for i in entry.items.iter_mut().filter(|i| is_measurable(&i.id)) {
+        i.kind = CheckResultKind::Unmeasurable;
+    }
+    let (score, measured_count, checked_count) =
+        recalculate_score(&entry.items, &entry.weights, true);
+    entry.score = score;
+    entry.measured_count = measured_count;
+    entry.checked_count = checked_count;
+}
+
+/// Postgame checklist according to the user's scoring settings; `None` when the game is skipped.
+pub fn checklist_for_game(
+    stats: PostGameStats,
+    auto_saved: bool,
+    settings: &GameScoringSettings,
+    rules: &ChecklistRules,
+) -> Option<SavedChecklist> {
+    let action = scoring_action(classify(&stats), settings);
+    if action == ScoringAction::Skip {
+        return None;
+    }
+    let mut entry = to_saved_checklist_with(stats, auto_saved, rules);
+    if action == ScoringAction::Unscored {
+        mark_unscored(&mut entry);
+    }
+    Some(entry)
+}
+
 pub fn merge_user_edits(target: &mut SavedChecklist, source: &SavedChecklist) {
     for t_item in &mut target.items {
         if let Some(s_item) = source.items.iter().find(|i| i.id == t_item.id) {
@@ -229,15 +437,38 @@ pub fn merge_user_edits(target: &mut SavedChecklist, source: &SavedChecklist) {
     if !source.notes.is_empty() {
         target.notes = source.notes.clone();
     }
-    let (score, measured_count, checked_count) = recalculate_score(&target.items);
+    if !source.tags.is_empty() {
+        target.tags = source.tags.clone();
+    }
+    let (score, measured_count, checked_count) =
+        recalculate_score(&target.items, &target.weights, target.unscored);
     target.score = score;
     target.measured_count = measured_count;
     target.checked_count = checked_count;
 }
 
+/// Recompute the score of a saved checklist with another weight set.
+pub fn rescore(entry: &mut SavedChecklist, weights: &ScoreWeights) {
+    entry.weights = weights.clone();
+    let (score, measured_count, checked_count) =
+        recalculate_score(&entry.items, weights, entry.unscored);
+    entry.score = score;
+    entry.measured_count = measured_count;
+    entry.checked_count = checked_count;
+}
+
 pub fn to_saved_checklist(stats: PostGameStats, auto_saved: bool) -> SavedChecklist {
-    let items = evaluate(stats.clone());
-    let (score, measured_count, checked_count) = recalculate_score(&items);
+    to_saved_checklist_with(stats, auto_saved, &load_rules())
+}
+
+pub fn to_saved_checklist_with(
+    stats: PostGameStats,
+    auto_saved: bool,
+    rules: &ChecklistRules,
+) -> SavedChecklist {
+    let game_class = classify(&stats);
+    let items = evaluate_with(&stats, game_class.mode, rules);
+    let (score, measured_count, checked_count) = recalculate_score(&items, &rules.weights, false);
     let saved_at_ms = now_millis();
     SavedChecklist {
         id: format!("cl-{saved_at_ms}"),
@@ -249,7 +480,41 @@ pub fn to_saved_checklist(stats: PostGameStats, auto_saved: bool) -> SavedCheckl
         checked_count,
         stats,
         notes: String::new(),
+        timeline: Vec::new(),
+        game_class,
+        unscored: false,
+        ruleset_version: rules.version.clone(),
+        weights: rules.weights.clone(),
+        tags: Vec::new(),
+        focus: Vec::new(),
+        evaluator_version: EVALUATOR_VERSION,
+    }
+}
+
+/// Re-run the evaluation on `entry.stats`, keeping identity, timeline, weights and user edits.
+pub fn reevaluate(entry: &SavedChecklist) -> SavedChecklist {
+    let mut rules = load_rules();
+    rules.weights = entry.weights.clone();
+    reevaluate_with(entry, &rules, &load_companion_config().game_scoring)
+}
+
+/// Re-evaluate with `rules` (weights included), reclassifying the game under `settings`.
+/// Stored games the settings would now skip are kept, unscored.
+pub fn reevaluate_with(
+    entry: &SavedChecklist,
+    rules: &ChecklistRules,
+    settings: &GameScoringSettings,
+) -> SavedChecklist {
+    let mut next = to_saved_checklist_with(entry.stats.clone(), entry.auto_saved, rules);
+    next.id = entry.id.clone();
+    next.saved_at_ms = entry.saved_at_ms;
+    next.timeline = entry.timeline.clone();
+    next.focus = entry.focus.clone();
+    if scoring_action(next.game_class, settings) != ScoringAction::Evaluate {
+        mark_unscored(&mut next);
     }
+    merge_user_edits(&mut next, entry);
+    next
 }
 
 pub fn now_millis() -> i64 {
@@ -268,8 +533,7 @@ mod tests {
     fn perfect_stats() -> PostGameStats {
         PostGameStats {
             game_duration_seconds: 1800,
-            cs_at_5: Some(44),
-            cs_at_10: Some(90),
+            cs_milestones: [(5, 44), (10, 90)].into_iter().collect(),
             cs_total: 200,
             vision_wards_bought: 2,
             wards_placed: 18,
@@ -284,12 +548,183 @@ mod tests {
 
     #[test]
     fn simple_checklist_has_twelve_items() {
-        assert_eq!(evaluate(perfect_stats()).len(), 12);
+        assert_eq!(
+            evaluate_with(
+                &perfect_stats(),
+                GameMode::SummonersRift,
+                &ChecklistRules::default()
+            )
+            .len(),
+            12
+        );
+    }
+
+    #[test]
+    fn overrides_count_unless_the_entry_is_unscored() {
+        // Saved before CS@10 was measured, then set by hand.
+        let mut old = to_saved_checklist_with(perfect_stats(), true, &ChecklistRules::default());
+        let item = old.items.iter_mut().find(|i| i.id == "cs_at_10").unwrap();
+        item.kind = CheckResultKind::Unmeasurable;
+        item.detail = None;
+        item.user_kind = Some(CheckResultKind::Failed);
+        assert_eq!(effective_kind(item, false), CheckResultKind::Failed);
+        let (score, measured_count, _) = recalculate_score(&old.items, &old.weights, false);
+        assert_eq!((score < 100.0, measured_count), (true, 8));
+
+        mark_unscored(&mut old);
+        let item = old.items.iter().find(|i| i.id == "cs_at_10").unwrap();
+        assert_eq!(item.user_kind, Some(CheckResultKind::Failed));
+        assert_eq!(
+            effective_kind(item, old.unscored),
+            CheckResultKind::Unmeasurable
+        );
+        assert_eq!(old.measured_count, 0);
     }
 
     #[test]
     fn measured_count_is_eight() {
-        let saved = to_saved_checklist(perfect_stats(), true);
+        let saved = to_saved_checklist_with(perfect_stats(), true, &ChecklistRules::default());
         assert_eq!(saved.measured_count, 8);
+        assert_eq!(saved.ruleset_version, "default-1");
+        assert_eq!(saved.evaluator_version, EVALUATOR_VERSION);
+    }
+
+    #[test]
+    fn remakes_and_modes_follow_scoring_settings() {
+        let settings = GameScoringSettings::default();
+        let rules = ChecklistRules::default();
+        let remake = PostGameStats {
+            game_duration_seconds: 200,
+            ..perfect_stats()
+        };
+        assert!(checklist_for_game(remake.clone(), true, &settings, &rules).is_none());
+
+        let unscored = GameScoringSettings {
+            remake: ScoringAction::Unscored,
+            ..settings.clone()
+        };
+        let saved = checklist_for_game(remake, true, &unscored, &rules).unwrap();
+        assert!(saved.unscored);
+        assert_eq!(saved.measured_count, 0);
+
+        let aram = PostGameStats {
+            game_mode: "ARAM".into(),
+            deaths: 6,
+            ..perfect_stats()
+        };
+        let saved = checklist_for_game(aram, true, &settings, &rules).unwrap();
+        assert_eq!(saved.game_class.mode, GameMode::Aram);
+        assert_eq!(saved.measured_count, 1);
+        assert_eq!(saved.score, 50.0);
+    }
+
+    #[test]
+    fn supports_and_junglers_use_their_rule_sets() {
+        let support = PostGameStats {
+            position: "UTILITY".into(),
+            cs_total: 40,
+            vision_score: 70,
+            ..perfect_stats()
+        };
+        let rules = ChecklistRules::default();
+        let items = evaluate_with(&support, GameMode::SummonersRift, &rules);
+        let kind = |items: &[ChecklistItem], id: &str| {
+            items.iter().find(|i| i.id == id).map(|i| i.kind.clone())
+        };
+        assert_eq!(
+            kind(&items, "cs_per_min"),
+            Some(CheckResultKind::Unmeasurable)
+        );
+        assert_eq!(kind(&items, "vision_score"), Some(CheckResultKind::Checked));
+        assert_eq!(kind(&items, "pink_wards"), Some(CheckResultKind::Partial));
+        let (_, measured, _) = recalculate_score(&items, &ScoreWeights::default(), false);
+        assert_eq!(measured, 6);
+
+        let jungler = PostGameStats {
+            position: "JUNGLE".into(),
+            cs_milestones: [(5, 30), (10, 55)].into_iter().collect(),
+            epic_monster_kills: 2,
+            ..perfect_stats()
+        };
+        let items = evaluate_with(&jungler, GameMode::SummonersRift, &rules);
+        assert_eq!(kind(&items, "cs_at_5"), Some(CheckResultKind::Checked));
+        assert_eq!(kind(&items, "cs_at_10"), Some(CheckResultKind::Partial));
+        assert_eq!(
+            kind(&items, "objective_participation"),
+            Some(CheckResultKind::Checked)
+        );
+        assert_eq!(items.len(), 13);
+    }
+
+    #[test]
+    fn custom_items_are_scored() {
+        let custom_items = vec![
+            CustomItem {
+                id: "kp".into(),
+                label: "Kills + assists".into(),
+                expression: "kills + assists >= 10".into(),
+                threshold: None,
+                lower_is_better: false,
+            },
+            CustomItem {
+                id: "gold".into(),
+                label: "Gold / min".into(),
+                expression: "gold_earned / minutes".into(),
+                threshold: Some(Threshold {
+                    checked: 400.0,
+                    partial: 350.0,
+                }),
+                lower_is_better: false,
+            },
+        ];
+        let rules = ChecklistRules {
+            custom_items,
+            ..Default::default()
+        };
+        assert!(rules.validate().is_ok());
+        let stats = PostGameStats {
+            kills: 3,
+            gold_earned: 11_400,
+            ..perfect_stats()
+        };
+        let saved = to_saved_checklist_with(stats, true, &rules);
+        let gold = saved.items.iter().find(|i| i.id == "custom:gold").unwrap();
+        assert_eq!(gold.kind, CheckResultKind::Partial);
+        assert_eq!(gold.label.as_deref(), Some("Gold / min"));
+        assert_eq!(saved.measured_count, 10);
+        assert_eq!(saved.checked_count, 6);
+    }
+
+    #[test]
+    fn weights_change_the_score_not_the_counts() {
+        let saved = to_saved_checklist_with(perfect_stats(), true, &ChecklistRules::default());
+        assert_eq!(saved.score, 87.5);
+
+        let weights = ScoreWeights {
+            items: [
+                ("survival".to_string(), 3.0),
+                ("cs_per_min".to_string(), 0.0),
+            ]
+            .into_iter()
+            .collect(),
+            partial_credit: 0.0,
+        };
+        let mut rescored = saved.clone();
+        rescore(&mut rescored, &weights);
+        // 5 checked at 1 + survival at 3 out of 9, wards_placed partial earns nothing.
+        assert!((rescored.score - 800.0 / 9.0).abs() < 0.01);
+        assert_eq!(rescored.measured_count, saved.measured_count);
+        assert_eq!(rescored.weights, weights);
+
+        let rules = ChecklistRules {
+            weights: ScoreWeights {
+                items: [("minimap".to_string(), 2.0)].into_iter().collect(),
+                partial_credit: 1.5,
+            },
+            ..Default::default()
+        };
+        let err = rules.validate().unwrap_err();
+        assert!(err.contains("weights.items.minimap"));
+        assert!(err.contains("partialCredit"));
     }
 }
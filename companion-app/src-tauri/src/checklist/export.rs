@@ -0,0 +1,360 @@
+//! Checklist history export: CSV, JSON Lines and a Markdown / HTML report for coaches.
+
+use super::evaluator::{effective_kind, CheckResultKind, SavedChecklist};
+use crate::postgame::PostGameStats;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    Markdown,
+    Html,
+}
+
+/// Games to export: the listed ids, else everything in the date range.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportSelection {
+    pub ids: Vec<String>,
+    pub from_ms: Option<i64>,
+    pub to_ms: Option<i64>,
+}
+
+/// Selected entries, oldest first.
+pub fn select(history: Vec<SavedChecklist>, selection: &ExportSelection) -> Vec<SavedChecklist> {
+    let mut entries: Vec<SavedChecklist> = history
+        .into_iter()
+        .filter(|e| {
+            if !selection.ids.is_empty() {
+                return selection.ids.contains(&e.id);
+            }
+            selection.from_ms.is_none_or(|from| e.saved_at_ms >= from)
+                && selection.to_ms.is_none_or(|to| e.saved_at_ms <= to)
+        })
+        .collect();
+    entries.sort_by_key(|e| e.saved_at_ms);
+    entries
+}
+
+fn kind_label(kind: &CheckResultKind) -> &'static str {
+    match kind {
+        CheckResultKind::Checked => "checked",
+        CheckResultKind::Partial => "partial",
+        CheckResultKind::Failed => "failed",
+        CheckResultKind::Unmeasurable => "unmeasurable",
+    }
+}
+
+/// `2026-03-14 18:05 UTC`.
+fn format_utc(ms: i64) -> String {
+    let secs = ms.div_euclid(1000);
+    let days = secs.div_euclid(86_400);
+    let time = secs.rem_euclid(86_400);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        time / 3600,
+        time % 3600 / 60
+    )
+}
+
+/// Quoted when needed; text a spreadsheet would run as a formula (`=`, `+`, `-`, `@`, tab,
+/// carriage return) gets a leading `'`. Numbers such as `-18` are left as they are.
+fn csv_field(value: &str) -> String {
+    let value =
+        if value.starts_with(['=', '+', '-', '@', '\t', '\r']) && value.parse::<f64>().is_err() {
+            format!("'{value}")
+        } else {
+            value.to_string()
+        };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => Some(String::new()),
+        Value::Bool(b) => Some(b.to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::String(s) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Scalar `PostGameStats` columns (camelCase), CS milestones included.
+fn stat_columns(entries: &[SavedChecklist]) -> Vec<String> {
+    let mut columns: Vec<String> = Vec::new();
+    let defaults = serde_json::to_value(PostGameStats::default()).unwrap_or(Value::Null);
+    let rows = std::iter::once(defaults).chain(
+        entries
+            .iter()
+            .filter_map(|e| serde_json::to_value(&e.stats).ok()),
+    );
+    for row in rows {
+        let Value::Object(map) = row else { continue };
+        for (key, value) in map {
+            if scalar_text(&value).is_some() && !columns.contains(&key) {
+                columns.push(key);
+            }
+        }
+    }
+    columns
+}
+
+/// One row per game: entry fields, flattened stats, then one column per item.
+pub fn to_csv(entries: &[SavedChecklist]) -> String {
+    let stat_cols = stat_columns(entries);
+    let mut item_cols: Vec<String> = Vec::new();
+    for item in entries.iter().flat_map(|e| &e.items) {
+        if !item_cols.contains(&item.id) {
+            item_cols.push(item.id.clone());
+        }
+    }
+    let mut header = vec![
+        "id",
+        "savedAt",
+        "score",
+        "measuredCount",
+        "checkedCount",
+        "unscored",
+        "rulesetVersion",
+        "tags",
+        "notes",
+    ]
+    .into_iter()
+    .map(str::to_string)
+    .collect::<Vec<_>>();
+    header.extend(stat_cols.iter().map(|c| format!("stats.{c}")));
+    header.extend(item_cols.iter().map(|c| format!("item.{c}")));
+
+    let mut out = header
+        .iter()
+        .map(|h| csv_field(h))
+        .collect::<Vec<_>>()
+        .join(",");
+    out.push('\n');
+    for entry in entries {
+        let mut row = vec![
+            entry.id.clone(),
+            format_utc(entry.saved_at_ms),
+            format!("{:.1}", entry.score),
+            entry.measured_count.to_string(),
+            entry.checked_count.to_string(),
+            entry.unscored.to_string(),
+            entry.ruleset_version.clone(),
+            entry.tags.join(" "),
+            entry.notes.clone(),
+        ];
+        let stats = serde_json::to_value(&entry.stats).unwrap_or(Value::Null);
+        row.extend(
+            stat_cols
+                .iter()
+                .map(|c| stats.get(c).and_then(scalar_text).unwrap_or_default()),
+        );
+        row.extend(item_cols.iter().map(|id| {
+            entry
+                .items
+                .iter()
+                .find(|i| &i.id == id)
+                .map(|i| kind_label(&effective_kind(i, entry.unscored)).to_string())
+                .unwrap_or_default()
+        }));
+        out.push_str(
+            &row.iter()
+                .map(|f| csv_field(f))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        out.push('\n');
+    }
+    out
+}
+
+pub fn to_jsonl(entries: &[SavedChecklist]) -> Result<String, String> {
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&serde_json::to_string(entry).map_err(|e| e.to_string())?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn result_label(entry: &SavedChecklist) -> &'static str {
+    if entry.stats.win {
+        "Win"
+    } else {
+        "Loss"
+    }
+}
+
+fn item_name(item: &super::evaluator::ChecklistItem) -> String {
+    item.label.clone().unwrap_or_else(|| item.id.clone())
+}
+
+fn summary_line(entry: &SavedChecklist) -> String {
+    let s = &entry.stats;
+    let mins = s.game_duration_seconds.max(1) as f32 / 60.0;
+    format!(
+        "Champion {} · {} · {}/{}/{} · {:.1} CS/min · {:.0} min · score {:.0}%",
+        s.champion_id,
+        result_label(entry),
+        s.kills,
+        s.deaths,
+        s.assists,
+        s.cs_total as f32 / mins,
+        mins,
+        entry.score
+    )
+}
+
+pub fn to_markdown(entries: &[SavedChecklist]) -> String {
+    let mut out = format!("# Checklist report\n\n{} games\n", entries.len());
+    for entry in entries {
+        out.push_str(&format!("\n## {}\n\n", format_utc(entry.saved_at_ms)));
+        out.push_str(&format!("{}\n\n", summary_line(entry)));
+        if !entry.stats.queue_type.is_empty() {
+            out.push_str(&format!("Queue: {}\n\n", entry.stats.queue_type));
+        }
+        out.push_str("| Item | Result | Detail |\n|---|---|---|\n");
+        for item in &entry.items {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                item_name(item).replace('|', "\\|"),
+                kind_label(&effective_kind(item, entry.unscored)),
+                item.detail.as_deref().unwrap_or("").replace('|', "\\|")
+            ));
+        }
+        if !entry.notes.is_empty() {
+            out.push_str(&format!("\n> {}\n", entry.notes.replace('\n', "\n> ")));
+        }
+    }
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn to_html(entries: &[SavedChecklist]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Checklist report</title>\
+         <style>body{font-family:sans-serif;max-width:820px;margin:auto}\
+         table{border-collapse:collapse;width:100%}td,th{border:1px solid #ccc;padding:4px 8px}\
+         .checked{color:#1a7f37}.partial{color:#9a6700}.failed{color:#cf222e}</style>\
+         </head><body>\n",
+    );
+    out.push_str(&format!(
+        "<h1>Checklist report</h1>\n<p>{} games</p>\n",
+        entries.len()
+    ));
+    for entry in entries {
+        out.push_str(&format!(
+            "<h2>{}</h2>\n<p>{}</p>\n<table><tr><th>Item</th><th>Result</th><th>Detail</th></tr>\n",
+            format_utc(entry.saved_at_ms),
+            html_escape(&summary_line(entry))
+        ));
+        for item in &entry.items {
+            let kind = kind_label(&effective_kind(item, entry.unscored));
+            out.push_str(&format!(
+                "<tr><td>{}</td><td class=\"{kind}\">{kind}</td><td>{}</td></tr>\n",
+                html_escape(&item_name(item)),
+                html_escape(item.detail.as_deref().unwrap_or(""))
+            ));
+        }
+        out.push_str("</table>\n");
+        if !entry.notes.is_empty() {
+            out.push_str(&format!(
+                "<blockquote>{}</blockquote>\n",
+                html_escape(&entry.notes).replace('\n', "<br>")
+            ));
+        }
+    }
+    out.push_str("</body></html>\n");
+    out
+}
+
+pub fn render(entries: &[SavedChecklist], format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Csv => Ok(to_csv(entries)),
+        ExportFormat::Jsonl => to_jsonl(entries),
+        ExportFormat::Markdown => Ok(to_markdown(entries)),
+        ExportFormat::Html => Ok(to_html(entries)),
+    }
+}
+
+/// Write the selected games to `path`; returns how many were exported.
+pub fn export_to(
+    history: Vec<SavedChecklist>,
+    selection: &ExportSelection,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<u32, String> {
+    let entries = select(history, selection);
+    if entries.is_empty() {
+        return Err("No checklist matches the selection".into());
+    }
+    let content = render(&entries, format)?;
+    std::fs::write(path, content).map_err(|e| format!("Cannot write {}: {e}", path.display()))?;
+    Ok(entries.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+
+    #[test]
+    fn exports_selected_games_in_every_format() {
+        let mut first = test_checklist(Default::default());
+        first.id = "cl-1".into();
+        first.saved_at_ms = 1_773_511_500_000;
+        first.notes = "Good, \"clean\" laning".into();
+        let mut second = first.clone();
+        second.id = "cl-2".into();
+        second.saved_at_ms = 1_773_600_000_000;
+
+        let only_first = ExportSelection {
+            to_ms: Some(1_773_511_500_000),
+            ..Default::default()
+        };
+        let entries = select(vec![second, first], &only_first);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(format_utc(entries[0].saved_at_ms), "2026-03-14 18:05 UTC");
+
+        let csv = to_csv(&entries);
+        let mut lines = csv.lines();
+        let header = lines.next().unwrap();
+        assert!(header.contains("stats.csTotal") && header.contains("item.survival"));
+        assert!(lines
+            .next()
+            .unwrap()
+            .contains("\"Good, \"\"clean\"\" laning\""));
+        assert_eq!(csv_field("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(csv_field("-18"), "-18");
+        assert_eq!(csv_field("\t=1+1"), "'\t=1+1");
+        assert_eq!(csv_field("\r=1+1"), "\"'\r=1+1\"");
+
+        assert_eq!(to_jsonl(&entries).unwrap().lines().count(), 1);
+        assert!(to_markdown(&entries).contains("| survival | checked |"));
+        assert!(to_html(&entries).contains("&quot;clean&quot;"));
+    }
+}
@@ -0,0 +1,445 @@
+//! Small arithmetic / comparison language for custom checklist items (`kills + assists >= 10`).
+//!
+//! Variables are `PostGameStats` fields in snake_case, plus `minutes`, `cs_at_<minute>`,
+//! `damage_to_champions` and `damage_taken`. Booleans are 1 / 0. Participant fields
+//! (`cs`, `vision_score`, …) are also available for the lane opponent (`opponent_cs`) and
+//! as team averages (`team_avg_vision_score`, `enemy_avg_gold_earned`).
+
+use crate::postgame::{PostGameParticipant, PostGameStats};
+use serde_json::Value;
+
+const MAX_LEN: usize = 500;
+const MAX_DEPTH: usize = 32;
+const FUNCTIONS: &[&str] = &["min", "max", "abs"];
+const PARTICIPANT_FIELDS: &[&str] = &[
+    "kills",
+    "deaths",
+    "assists",
+    "cs",
+    "gold_earned",
+    "damage_to_champions",
+    "damage_taken",
+    "vision_score",
+    "wards_placed",
+    "wards_killed",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Num(f64),
+    Var(String),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+const OPERATORS: &[&str] = &[
+    "&&", "||", ">=", "<=", "==", "!=", ">", "<", "+", "-", "*", "/", "%", "!",
+];
+
+fn tokenize(src: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text
+                .parse()
+                .map_err(|_| format!("Invalid number `{text}`"))?;
+            tokens.push(Token::Num(n));
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let op = OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format!("Unexpected character `{c}`"))?;
+            tokens.push(Token::Op(op));
+            i += op.len();
+        }
+    }
+    Ok(tokens)
+}
+
+/// Binary operators from loosest to tightest binding.
+const LEVELS: &[&[&str]] = &[
+    &["||"],
+    &["&&"],
+    &["==", "!="],
+    &[">=", "<=", ">", "<"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), String> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            _ => Err(format!("Expected {token:?}")),
+        }
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(Token::Op(op)) = self.peek() {
+            let op = *op;
+            if !LEVELS[level].contains(&op) {
+                break;
+            }
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("Expression is nested too deeply".into());
+        }
+        let expr = match self.next() {
+            Some(Token::Op("-")) => Expr::Neg(Box::new(self.unary()?)),
+            Some(Token::Op("!")) => Expr::Not(Box::new(self.unary()?)),
+            Some(Token::Num(n)) => Expr::Num(n),
+            Some(Token::LParen) => {
+                let inner = self.binary(0)?;
+                self.expect(Token::RParen)?;
+                inner
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                if !FUNCTIONS.contains(&name.as_str()) {
+                    return Err(format!("Unknown function `{name}`"));
+                }
+                self.pos += 1;
+                let mut args = vec![self.binary(0)?];
+                while self.peek() == Some(&Token::Comma) {
+                    self.pos += 1;
+                    args.push(self.binary(0)?);
+                }
+                self.expect(Token::RParen)?;
+                Expr::Call(name, args)
+            }
+            Some(Token::Ident(name)) => Expr::Var(name),
+            Some(token) => return Err(format!("Unexpected {token:?}")),
+            None => return Err("Unexpected end of expression".into()),
+        };
+        self.depth -= 1;
+        Ok(expr)
+    }
+}
+
+pub fn parse(src: &str) -> Result<Expr, String> {
+    if src.len() > MAX_LEN {
+        return Err(format!("Expression longer than {MAX_LEN} characters"));
+    }
+    let mut parser = Parser {
+        tokens: tokenize(src)?,
+        pos: 0,
+        depth: 0,
+    };
+    let expr = parser.binary(0)?;
+    match parser.peek() {
+        None => Ok(expr),
+        Some(token) => Err(format!("Unexpected {token:?}")),
+    }
+}
+
+fn camel_case(snake: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in snake.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn participant_field(p: &PostGameParticipant, field: &str) -> Option<f64> {
+    let value = match field {
+        "kills" => p.kills,
+        "deaths" => p.deaths,
+        "assists" => p.assists,
+        "cs" => p.cs,
+        "gold_earned" => p.gold_earned,
+        "damage_to_champions" => p.damage_to_champions,
+        "damage_taken" => p.damage_taken,
+        "vision_score" => p.vision_score,
+        "wards_placed" => p.wards_placed,
+        "wards_killed" => p.wards_killed,
+        _ => return None,
+    };
+    Some(f64::from(value))
+}
+
+/// `opponent_<field>`, `team_avg_<field>` or `enemy_avg_<field>`; `None` for other names.
+fn split_participant_var(name: &str) -> Option<(&str, &str)> {
+    ["opponent_", "team_avg_", "enemy_avg_"]
+        .into_iter()
+        .find_map(|prefix| Some((prefix, name.strip_prefix(prefix)?)))
+        .filter(|(_, field)| PARTICIPANT_FIELDS.contains(field))
+}
+
+/// Numeric values an expression can read from a game.
+pub struct Variables<'a> {
+    stats: &'a PostGameStats,
+    fields: Value,
+}
+
+impl<'a> Variables<'a> {
+    pub fn new(stats: &'a PostGameStats) -> Self {
+        Self {
+            stats,
+            fields: serde_json::to_value(stats).unwrap_or(Value::Null),
+        }
+    }
+
+    /// `None` for unknown names and values the game does not have (e.g. no CS at 15).
+    pub fn get(&self, name: &str) -> Option<f64> {
+        if let Some(minute) = name.strip_prefix("cs_at_") {
+            return self.stats.cs_at(minute.parse().ok()?).map(f64::from);
+        }
+        if let Some((prefix, field)) = split_participant_var(name) {
+            return match prefix {
+                "opponent_" => participant_field(self.stats.lane_opponent()?, field),
+                ally => self
+                    .stats
+                    .team_average(ally == "team_avg_", |p| {
+                        participant_field(p, field).unwrap_or(0.0) as f32
+                    })
+                    .map(f64::from),
+            };
+        }
+        let local = self.stats.local_participant();
+        match name {
+            "minutes" => return Some(self.stats.game_duration_seconds.max(1) as f64 / 60.0),
+            "damage_to_champions" => return local.map(|p| p.damage_to_champions as f64),
+            "damage_taken" => return local.map(|p| p.damage_taken as f64),
+            _ => {}
+        }
+        match self.fields.get(camel_case(name))? {
+            Value::Number(n) => n.as_f64(),
+            Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+
+    /// Whether `name` can ever have a value (used to validate rules).
+    pub fn is_known(name: &str) -> bool {
+        if let Some(minute) = name.strip_prefix("cs_at_") {
+            return minute.parse::<u32>().is_ok();
+        }
+        if matches!(name, "minutes" | "damage_to_champions" | "damage_taken")
+            || split_participant_var(name).is_some()
+        {
+            return true;
+        }
+        let fields = serde_json::to_value(PostGameStats::default()).unwrap_or(Value::Null);
+        let key = camel_case(name);
+        name == name.to_ascii_lowercase()
+            && fields.get(&key).is_some()
+            && !matches!(
+                fields[&key],
+                Value::String(_) | Value::Array(_) | Value::Object(_)
+            )
+    }
+}
+
+/// Variable names used by `expr`.
+pub fn variables(expr: &Expr, out: &mut Vec<String>) {
+    match expr {
+        Expr::Num(_) => {}
+        Expr::Var(name) => out.push(name.clone()),
+        Expr::Neg(e) | Expr::Not(e) => variables(e, out),
+        Expr::Binary(_, a, b) => {
+            variables(a, out);
+            variables(b, out);
+        }
+        Expr::Call(_, args) => args.iter().for_each(|a| variables(a, out)),
+    }
+}
+
+fn truthy(v: f64) -> bool {
+    v != 0.0
+}
+
+fn bool_num(b: bool) -> f64 {
+    if b {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+pub fn eval(expr: &Expr, vars: &Variables) -> Result<f64, String> {
+    let value = match expr {
+        Expr::Num(n) => *n,
+        Expr::Var(name) => vars
+            .get(name)
+            .ok_or_else(|| format!("No value for `{name}`"))?,
+        Expr::Neg(e) => -eval(e, vars)?,
+        Expr::Not(e) => bool_num(!truthy(eval(e, vars)?)),
+        Expr::Binary(op, a, b) => {
+            let a = eval(a, vars)?;
+            let b = eval(b, vars)?;
+            match *op {
+                "||" => bool_num(truthy(a) || truthy(b)),
+                "&&" => bool_num(truthy(a) && truthy(b)),
+                "==" => bool_num(a == b),
+                "!=" => bool_num(a != b),
+                ">=" => bool_num(a >= b),
+                "<=" => bool_num(a <= b),
+                ">" => bool_num(a > b),
+                "<" => bool_num(a < b),
+                "+" => a + b,
+                "-" => a - b,
+                "*" => a * b,
+                "/" | "%" if b == 0.0 => return Err("Division by zero".into()),
+                "/" => a / b,
+                "%" => a % b,
+                _ => return Err(format!("Unknown operator `{op}`")),
+            }
+        }
+        Expr::Call(name, args) => {
+            let args = args
+                .iter()
+                .map(|a| eval(a, vars))
+                .collect::<Result<Vec<_>, _>>()?;
+            match (name.as_str(), args.as_slice()) {
+                ("abs", [x]) => x.abs(),
+                ("min", [first, rest @ ..]) => rest.iter().fold(*first, |m, x| m.min(*x)),
+                ("max", [first, rest @ ..]) => rest.iter().fold(*first, |m, x| m.max(*x)),
+                _ => return Err(format!("Bad arguments for `{name}`")),
+            }
+        }
+    };
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err("Result is not a number".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(
+        team_id: u32,
+        position: &str,
+        cs: u32,
+        vision_score: u32,
+        is_local: bool,
+    ) -> PostGameParticipant {
+        PostGameParticipant {
+            team_id: Some(team_id),
+            position: position.into(),
+            cs,
+            vision_score,
+            is_local,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn evaluates_over_stats() {
+        let stats = PostGameStats {
+            kills: 6,
+            assists: 5,
+            deaths: 0,
+            gold_earned: 12_600,
+            game_duration_seconds: 1800,
+            win: true,
+            cs_milestones: [(10, 82)].into_iter().collect(),
+            participants: vec![
+                participant(100, "MIDDLE", 200, 30, true),
+                participant(100, "UTILITY", 40, 10, false),
+                participant(200, "MIDDLE", 150, 20, false),
+            ],
+            ..Default::default()
+        };
+        let vars = Variables::new(&stats);
+        let run = |src: &str| eval(&parse(src).unwrap(), &vars);
+        assert_eq!(run("kills + assists >= 10"), Ok(1.0));
+        assert_eq!(run("gold_earned / minutes"), Ok(420.0));
+        assert_eq!(run("(kills + assists) / max(deaths, 1)"), Ok(11.0));
+        assert_eq!(run("win && cs_at_10 > 80 && !leaver"), Ok(1.0));
+        assert_eq!(run("2 + 3 * 4 - -1"), Ok(15.0));
+        assert!(run("kills / deaths").is_err());
+        assert!(run("cs_at_15 > 100").is_err());
+        assert_eq!(run("opponent_cs"), Ok(150.0));
+        assert_eq!(run("team_avg_vision_score"), Ok(20.0));
+        assert_eq!(run("enemy_avg_cs"), Ok(150.0));
+        assert!(eval(
+            &parse("opponent_cs").unwrap(),
+            &Variables::new(&PostGameStats::default())
+        )
+        .is_err());
+
+        assert!(parse("kills +").is_err());
+        assert!(parse("system(1)").is_err());
+        assert!(Variables::is_known("vision_score"));
+        assert!(!Variables::is_known("player_rank"));
+        assert!(!Variables::is_known("nope"));
+        assert!(Variables::is_known("opponent_damage_to_champions"));
+        assert!(!Variables::is_known("opponent_items"));
+    }
+}
@@ -0,0 +1,258 @@
+//! Pre-game focus points: the weakest checklist items of the player's recent games.
+
+use super::analytics::item_pass_rates;
+use super::evaluator::{effective_kind, CheckResultKind, SavedChecklist};
+use super::query::{query_in, HistoryQuery};
+use crate::db;
+use rusqlite::Connection;
+use serde::Serialize;
+
+/// Recent games the pass rates are computed over.
+pub const FOCUS_GAMES: usize = 10;
+pub const FOCUS_COUNT: usize = 2;
+/// Fewer matching games than this and the next, wider scope is used.
+const MIN_GAMES: usize = 3;
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FocusScope {
+    ChampionRole,
+    Champion,
+    Role,
+    All,
+}
+
+/// Payload of `checklist:focus`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusSuggestion {
+    pub id: String,
+    /// Display name of custom items.
+    pub label: Option<String>,
+    pub pass_rate: f32,
+    pub measured: u32,
+    pub scope: FocusScope,
+}
+
+/// Payload of `checklist:focus-review`: how the focus item went compared to before.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusReview {
+    pub id: String,
+    pub kind: CheckResultKind,
+    pub previous_pass_rate: f32,
+    /// Checked this game, or partial when it was usually failed.
+    pub improved: bool,
+}
+
+/// Lowest pass-rate items over the last games on this champion and role, widening the
+/// scope (champion, role, every game) when there are too few.
+pub fn suggest(
+    history: &[SavedChecklist],
+    champion_id: Option<u32>,
+    position: &str,
+) -> Vec<FocusSuggestion> {
+    let mut scored: Vec<&SavedChecklist> = history.iter().filter(|e| !e.unscored).collect();
+    scored.sort_by_key(|e| std::cmp::Reverse(e.saved_at_ms));
+    let in_scope = |scope: FocusScope, e: &SavedChecklist| {
+        let same_champion = Some(e.stats.champion_id) == champion_id;
+        let same_role = !position.is_empty() && e.stats.position == position;
+        match scope {
+            FocusScope::ChampionRole => same_champion && same_role,
+            FocusScope::Champion => same_champion,
+            FocusScope::Role => same_role,
+            FocusScope::All => true,
+        }
+    };
+    let scopes = [
+        FocusScope::ChampionRole,
+        FocusScope::Champion,
+        FocusScope::Role,
+        FocusScope::All,
+    ];
+    for scope in scopes {
+        let games: Vec<&SavedChecklist> = scored
+            .iter()
+            .copied()
+            .filter(|e| in_scope(scope, e))
+            .take(FOCUS_GAMES)
+            .collect();
+        if games.len() < MIN_GAMES && scope != FocusScope::All {
+            continue;
+        }
+        let mut rates: Vec<_> = item_pass_rates(&games)
+            .into_iter()
+            .filter(|r| r.pass_rate < 1.0)
+            .collect();
+        rates.sort_by(|a, b| {
+            a.pass_rate
+                .total_cmp(&b.pass_rate)
+                .then(b.measured.cmp(&a.measured))
+        });
+        return rates
+            .into_iter()
+            .take(FOCUS_COUNT)
+            .map(|r| FocusSuggestion {
+                label: games
+                    .iter()
+                    .flat_map(|g| &g.items)
+                    .find_map(|i| (i.id == r.id).then(|| i.label.clone()).flatten()),
+                id: r.id,
+                pass_rate: r.pass_rate,
+                measured: r.measured,
+                scope,
+            })
+            .collect();
+    }
+    Vec::new()
+}
+
+pub(crate) fn recent_games_in(
+    conn: &Connection,
+    champion_id: Option<u32>,
+    position: &str,
+) -> Result<Vec<SavedChecklist>, String> {
+    let position = Some(position.to_string()).filter(|p| !p.is_empty());
+    let mut scopes = Vec::new();
+    if champion_id.is_some() {
+        if position.is_some() {
+            scopes.push(HistoryQuery {
+                champion_id,
+                position: position.clone(),
+                ..Default::default()
+            });
+        }
+        scopes.push(HistoryQuery {
+            champion_id,
+            ..Default::default()
+        });
+    }
+    if position.is_some() {
+        scopes.push(HistoryQuery {
+            position,
+            ..Default::default()
+        });
+    }
+    scopes.push(HistoryQuery::default());
+    let mut games: Vec<SavedChecklist> = Vec::new();
+    for scope in scopes {
+        let page = query_in(
+            conn,
+            &HistoryQuery {
+                scored_only: true,
+                limit: Some(FOCUS_GAMES as u32),
+                ..scope
+            },
+        )?;
+        for entry in page.entries {
+            if !games.iter().any(|g| g.id == entry.id) {
+                games.push(entry);
+            }
+        }
+    }
+    Ok(games)
+}
+
+/// The last `FOCUS_GAMES` scored games of every scope (champion and role, champion, role,
+/// overall): every game `suggest` can pick from, without loading the whole history.
+pub fn recent_games(
+    champion_id: Option<u32>,
+    position: &str,
+) -> Result<Vec<SavedChecklist>, String> {
+    recent_games_in(&db::connect()?, champion_id, position)
+}
+
+/// Result of each focus item in the saved game; items it could not measure are left out.
+pub fn review(focus: &[FocusSuggestion], saved: &SavedChecklist) -> Vec<FocusReview> {
+    focus
+        .iter()
+        .filter_map(|f| {
+            let item = saved.items.iter().find(|i| i.id == f.id)?;
+            let kind = effective_kind(item, saved.unscored);
+            let improved = match kind {
+                CheckResultKind::Checked => true,
+                CheckResultKind::Partial => f.pass_rate < 0.5,
+                CheckResultKind::Failed => false,
+                CheckResultKind::Unmeasurable => return None,
+            };
+            Some(FocusReview {
+                id: f.id.clone(),
+                kind,
+                previous_pass_rate: f.pass_rate,
+                improved,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+    use crate::postgame::PostGameStats;
+
+    fn game(saved_at_ms: i64, champion_id: u32, deaths: u32) -> SavedChecklist {
+        let stats = PostGameStats {
+            game_duration_seconds: 1800,
+            cs_total: 240,
+            champion_id,
+            deaths,
+            position: "MIDDLE".into(),
+            ..Default::default()
+        };
+        let mut entry = test_checklist(stats);
+        entry.saved_at_ms = saved_at_ms;
+        entry
+    }
+
+    #[test]
+    fn suggests_weakest_items_and_reviews_them() {
+        let mut history: Vec<SavedChecklist> = (0..4).map(|i| game(i, 103, 7)).collect();
+        history.push(game(10, 22, 0));
+
+        let focus = suggest(&history, Some(103), "MIDDLE");
+        assert_eq!(focus.len(), FOCUS_COUNT);
+        assert_eq!(focus[0].scope, FocusScope::ChampionRole);
+        assert!(focus
+            .iter()
+            .all(|f| f.pass_rate == 0.0 && f.id != "cs_per_min"));
+
+        let wider = suggest(&history, Some(22), "MIDDLE");
+        assert_eq!(wider[0].scope, FocusScope::Role);
+
+        let survival = FocusSuggestion {
+            id: "survival".into(),
+            label: None,
+            pass_rate: 0.0,
+            measured: 4,
+            scope: FocusScope::ChampionRole,
+        };
+        let reviewed = review(&[survival], &game(11, 103, 1));
+        assert_eq!(reviewed[0].kind, CheckResultKind::Checked);
+        assert!(reviewed[0].improved);
+        assert!(suggest(&[], None, "").is_empty());
+    }
+
+    #[test]
+    fn recent_games_cover_the_champion_in_the_role() {
+        let conn = crate::db::test_conn();
+        for i in 0..25 {
+            let mut entry = game(i, 103, 0);
+            entry.id = format!("cl-{i}");
+            if i < 5 {
+                entry.stats.position = "TOP".into();
+            } else if i >= 20 {
+                entry.unscored = true;
+            }
+            crate::checklist::store::upsert(&conn, &entry).unwrap();
+        }
+        let games = recent_games_in(&conn, Some(103), "TOP").unwrap();
+        assert_eq!(
+            games.iter().filter(|g| g.stats.position == "TOP").count(),
+            5
+        );
+        assert!(games.iter().all(|g| !g.unscored));
+        let focus = suggest(&games, Some(103), "TOP");
+        assert!(focus.iter().all(|f| f.scope == FocusScope::ChampionRole));
+    }
+}
@@ -0,0 +1,195 @@
+//! Merge a history file from another machine or a backup into the local store.
+
+use super::evaluator::{merge_user_edits, SavedChecklist};
+use super::store::{find_in, load_all_in, upsert};
+use crate::db::{self, sql_err};
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    pub added: u32,
+    pub updated: u32,
+    /// Already stored with every user edit, invalid, or matching a stored entry this version
+    /// cannot read (see `problems`).
+    pub skipped: u32,
+    pub problems: Vec<String>,
+}
+
+/// A JSON array of `SavedChecklist` (the JSON export) or one entry per line (JSON Lines).
+fn parse_entries(content: &str) -> Result<Vec<Value>, String> {
+    let content = content.trim_start_matches('\u{feff}').trim();
+    if content.starts_with('[') {
+        return serde_json::from_str(content).map_err(|e| format!("Invalid JSON array: {e}"));
+    }
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| format!("Invalid JSON on line {}: {e}", i + 1))
+        })
+        .collect()
+}
+
+fn validate(entry: &SavedChecklist) -> Result<(), String> {
+    if entry.id.trim().is_empty() {
+        return Err("missing id".into());
+    }
+    if entry.saved_at_ms <= 0 {
+        return Err(format!("{}: invalid savedAtMs", entry.id));
+    }
+    if !entry.score.is_finite() || !(0.0..=100.0).contains(&entry.score) {
+        return Err(format!("{}: score out of range", entry.id));
+    }
+    Ok(())
+}
+
+/// Add the incoming overrides, manual checks, notes and tags to the stored entry; stored
+/// overrides win conflicts. Returns whether anything changed.
+fn union_edits(stored: &mut SavedChecklist, incoming: &SavedChecklist) -> bool {
+    let before = serde_json::to_value(&*stored).ok();
+    let mut edits = incoming.clone();
+    for item in &mut edits.items {
+        if let Some(local) = stored.items.iter().find(|i| i.id == item.id) {
+            if local.user_kind.is_some() {
+                item.user_kind = local.user_kind.clone();
+            }
+            item.manual_checked |= local.manual_checked;
+        }
+    }
+    let (local, remote) = (stored.notes.trim(), incoming.notes.trim());
+    edits.notes = if remote.is_empty() || local.contains(remote) {
+        stored.notes.clone()
+    } else if local.is_empty() {
+        incoming.notes.clone()
+    } else {
+        format!("{local}\n{remote}")
+    };
+    edits.tags = stored.tags.clone();
+    for tag in &incoming.tags {
+        if !edits.tags.contains(tag) {
+            edits.tags.push(tag.clone());
+        }
+    }
+    merge_user_edits(stored, &edits);
+    serde_json::to_value(&*stored).ok() != before
+}
+
+/// Merge in one transaction. An entry matching a stored id or `stats.game_id` adds its user
+/// edits to the stored one.
+pub fn merge_in(conn: &mut Connection, content: &str) -> Result<ImportSummary, String> {
+    let values = parse_entries(content)?;
+    let mut summary = ImportSummary::default();
+    let tx = conn.transaction().map_err(sql_err)?;
+    for (index, value) in values.into_iter().enumerate() {
+        let entry = serde_json::from_value::<SavedChecklist>(value)
+            .map_err(|e| e.to_string())
+            .and_then(|entry| validate(&entry).map(|_| entry));
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                summary.skipped += 1;
+                summary.problems.push(format!("Entry {}: {e}", index + 1));
+                continue;
+            }
+        };
+        match find_in(&tx, &entry.id, entry.stats.game_id) {
+            Ok(None) => {
+                upsert(&tx, &entry)?;
+                summary.added += 1;
+            }
+            Ok(Some(mut stored)) => {
+                if union_edits(&mut stored, &entry) {
+                    upsert(&tx, &stored)?;
+                    summary.updated += 1;
+                } else {
+                    summary.skipped += 1;
+                }
+            }
+            Err(e) => {
+                summary.skipped += 1;
+                summary.problems.push(format!("Entry {}: {e}", index + 1));
+            }
+        }
+    }
+    tx.commit().map_err(sql_err)?;
+    Ok(summary)
+}
+
+/// Import `path` into the history; returns the merged history and the counts.
+pub fn import_from(path: &Path) -> Result<(Vec<SavedChecklist>, ImportSummary), String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    let mut conn = db::connect()?;
+    let summary = merge_in(&mut conn, &content)?;
+    Ok((load_all_in(&conn)?, summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+
+    fn entry(id: &str, game_id: u64) -> SavedChecklist {
+        let mut entry = test_checklist(Default::default());
+        entry.id = id.into();
+        entry.saved_at_ms = 1_773_511_500_000;
+        entry.stats.game_id = Some(game_id);
+        entry
+    }
+
+    #[test]
+    fn merges_user_edits() {
+        let mut conn = db::test_conn();
+        let mut local_edited = entry("cl-1", 1);
+        local_edited.notes = "local".into();
+        upsert(&conn, &local_edited).unwrap();
+        upsert(&conn, &entry("cl-2", 2)).unwrap();
+
+        let mut remote_edited = entry("other-2", 2);
+        remote_edited.items[0].manual_checked = true;
+        let mut bad = entry("cl-4", 4);
+        bad.saved_at_ms = 0;
+        let mut remote_noted = entry("cl-1", 1);
+        remote_noted.notes = "remote".into();
+        remote_noted.tags = vec!["duo".into()];
+        let lines = [remote_noted, remote_edited, entry("cl-3", 3), bad]
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let err = merge_in(&mut conn, &format!("{lines}\nnot json")).unwrap_err();
+        assert!(err.contains("line 5"));
+
+        let summary = merge_in(&mut conn, &lines).unwrap();
+        assert_eq!((summary.added, summary.updated, summary.skipped), (1, 2, 1));
+        assert_eq!(summary.problems.len(), 1);
+        assert_eq!(merge_in(&mut conn, &lines).unwrap().updated, 0);
+
+        let all = load_all_in(&conn).unwrap();
+        assert_eq!(all.len(), 3);
+        let merged = all.iter().find(|e| e.id == "cl-1").unwrap();
+        assert_eq!(
+            (merged.notes.as_str(), merged.tags.clone()),
+            ("local\nremote", vec!["duo".to_string()])
+        );
+        assert!(all.iter().find(|e| e.id == "cl-2").unwrap().items[0].manual_checked);
+
+        conn.execute(
+            "INSERT INTO checklists (id, saved_at_ms, game_id, data) VALUES ('cl-9', 9, 9, '{}')",
+            [],
+        )
+        .unwrap();
+        let unreadable = serde_json::to_string(&entry("other-9", 9)).unwrap();
+        let summary = merge_in(&mut conn, &unreadable).unwrap();
+        assert_eq!(summary.skipped, 1);
+        assert!(summary.problems[0].contains("cannot be read"));
+
+        let array = serde_json::to_string(&[entry("cl-5", 5)]).unwrap();
+        assert_eq!(merge_in(&mut conn, &array).unwrap().added, 1);
+    }
+}
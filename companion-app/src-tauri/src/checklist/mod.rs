@@ -1,5 +1,25 @@
+pub mod analytics;
+pub mod backfill;
 pub mod evaluator;
+pub mod export;
+pub mod expr;
+pub mod focus;
+pub mod import;
+pub mod query;
+pub mod rebuild;
+pub mod recovery;
+pub mod rescore;
+pub mod rules;
+pub mod session;
 pub mod store;
 
-pub use evaluator::{merge_user_edits, recalculate_score, to_saved_checklist, SavedChecklist};
-pub use store::{delete_entry, load_all, save_entry};
+pub use evaluator::{
+    checklist_for_game, merge_user_edits, recalculate_score, to_saved_checklist, SavedChecklist,
+};
+pub use store::{delete_entry, find, load_all, save_entry};
+
+/// Auto-saved checklist evaluated with the default rules, independent of the user's config.
+#[cfg(test)]
+pub(crate) fn test_checklist(stats: crate::postgame::PostGameStats) -> SavedChecklist {
+    evaluator::to_saved_checklist_with(stats, true, &rules::ChecklistRules::default())
+}
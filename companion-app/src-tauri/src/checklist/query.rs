@@ -0,0 +1,250 @@
+//! Filtered, sorted and paginated checklist history.
+
+use super::evaluator::SavedChecklist;
+use crate::db::{self, sql_err};
+use rusqlite::types::ToSql;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 500;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum HistorySort {
+    #[default]
+    SavedAt,
+    Score,
+}
+
+/// Every filter is optional; unset fields match everything.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryQuery {
+    pub from_ms: Option<i64>,
+    pub to_ms: Option<i64>,
+    pub champion_id: Option<u32>,
+    pub queue_id: Option<u32>,
+    /// Team position (`TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM`, `UTILITY`).
+    pub position: Option<String>,
+    pub win: Option<bool>,
+    pub ranked_only: bool,
+    /// Leave out unscored games (remakes and modes the scoring settings exclude).
+    pub scored_only: bool,
+    pub min_score: Option<f32>,
+    pub max_score: Option<f32>,
+    /// Case-insensitive search in the notes.
+    pub text: Option<String>,
+    /// Entries must carry all of these tags.
+    pub tags: Vec<String>,
+    pub sort: HistorySort,
+    pub ascending: bool,
+    pub offset: u32,
+    /// Page size (default 50, at most 500).
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPage {
+    pub entries: Vec<SavedChecklist>,
+    /// Matching entries across all pages (rows this version cannot read are not counted).
+    pub total: u32,
+    pub offset: u32,
+    pub limit: u32,
+}
+
+fn where_clause(q: &HistoryQuery) -> (String, Vec<Box<dyn ToSql>>) {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+    let mut add = |condition: &'static str, value: Box<dyn ToSql>| {
+        conditions.push(condition);
+        params.push(value);
+    };
+    if let Some(from) = q.from_ms {
+        add("saved_at_ms >= ?", Box::new(from));
+    }
+    if let Some(to) = q.to_ms {
+        add("saved_at_ms <= ?", Box::new(to));
+    }
+    if let Some(champion_id) = q.champion_id {
+        add("champion_id = ?", Box::new(champion_id));
+    }
+    if let Some(queue_id) = q.queue_id {
+        add("queue_id = ?", Box::new(queue_id));
+    }
+    if let Some(position) = q.position.as_deref().filter(|p| !p.is_empty()) {
+        add(
+            "json_extract(data, '$.stats.position') = ?",
+            Box::new(position.to_string()),
+        );
+    }
+    if let Some(win) = q.win {
+        add("win = ?", Box::new(win));
+    }
+    if q.ranked_only {
+        add("ranked = ?", Box::new(true));
+    }
+    if q.scored_only {
+        add(
+            "coalesce(json_extract(data, '$.unscored'), 0) = ?",
+            Box::new(false),
+        );
+    }
+    if let Some(min) = q.min_score {
+        add("score >= ?", Box::new(min));
+    }
+    if let Some(max) = q.max_score {
+        add("score <= ?", Box::new(max));
+    }
+    for tag in &q.tags {
+        add(
+            "EXISTS (SELECT 1 FROM json_each(data, '$.tags') WHERE value = ?)",
+            Box::new(tag.clone()),
+        );
+    }
+    if conditions.is_empty() {
+        return (String::new(), params);
+    }
+    (format!(" WHERE {}", conditions.join(" AND ")), params)
+}
+
+pub(crate) fn query_in(conn: &Connection, q: &HistoryQuery) -> Result<HistoryPage, String> {
+    let limit = q.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let (filter, params) = where_clause(q);
+    let param_refs: Vec<&dyn ToSql> = params.iter().map(|p| p.as_ref()).collect();
+    // Notes are matched here rather than in SQL, whose `lower()` only folds ASCII.
+    let text = q
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_lowercase);
+
+    let column = match q.sort {
+        HistorySort::SavedAt => "saved_at_ms",
+        HistorySort::Score => "score",
+    };
+    let direction = if q.ascending { "ASC" } else { "DESC" };
+    let sql = format!(
+        "SELECT data FROM checklists{filter}
+         ORDER BY {column} {direction}, saved_at_ms {direction}"
+    );
+    let mut stmt = conn.prepare(&sql).map_err(sql_err)?;
+    let rows = stmt
+        .query_map(param_refs.as_slice(), |row| row.get::<_, String>(0))
+        .map_err(sql_err)?;
+    let mut matching = Vec::new();
+    for raw in rows {
+        let Ok(entry) = serde_json::from_str::<SavedChecklist>(&raw.map_err(sql_err)?) else {
+            continue;
+        };
+        if text
+            .as_ref()
+            .is_none_or(|t| entry.notes.to_lowercase().contains(t))
+        {
+            matching.push(entry);
+        }
+    }
+    let total = matching.len() as u32;
+    let entries = matching
+        .into_iter()
+        .skip(q.offset as usize)
+        .take(limit as usize)
+        .collect();
+    Ok(HistoryPage {
+        entries,
+        total,
+        offset: q.offset,
+        limit,
+    })
+}
+
+pub fn query(q: &HistoryQuery) -> Result<HistoryPage, String> {
+    query_in(&db::connect()?, q)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::store::upsert;
+    use crate::checklist::test_checklist;
+
+    #[test]
+    fn filters_sorts_and_paginates() {
+        let conn = db::test_conn();
+        for i in 0..5u32 {
+            let mut entry = test_checklist(Default::default());
+            entry.id = format!("cl-{i}");
+            entry.saved_at_ms = i64::from(i) * 1000;
+            entry.score = (i * 20) as f32;
+            entry.stats.champion_id = if i % 2 == 0 { 22 } else { 51 };
+            entry.stats.win = i >= 2;
+            entry.stats.position = if i == 1 { "JUNGLE" } else { "MIDDLE" }.into();
+            entry.notes = if i == 3 {
+                "Tilted after bot dive".into()
+            } else if i == 0 {
+                "ÉCHANGES perdus".into()
+            } else {
+                String::new()
+            };
+            entry.tags = if i == 4 {
+                vec!["duo".into()]
+            } else {
+                Vec::new()
+            };
+            upsert(&conn, &entry).unwrap();
+        }
+
+        let page = query_in(&conn, &HistoryQuery::default()).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.entries[0].id, "cl-4");
+
+        let ashe_wins = HistoryQuery {
+            champion_id: Some(22),
+            win: Some(true),
+            sort: HistorySort::Score,
+            ascending: true,
+            limit: Some(1),
+            ..Default::default()
+        };
+        let page = query_in(&conn, &ashe_wins).unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(page.entries[0].id, "cl-2");
+
+        let search = HistoryQuery {
+            text: Some("tilted".into()),
+            ..Default::default()
+        };
+        assert_eq!(query_in(&conn, &search).unwrap().entries[0].id, "cl-3");
+        let accented = HistoryQuery {
+            text: Some("échanges".into()),
+            ..Default::default()
+        };
+        assert_eq!(query_in(&conn, &accented).unwrap().entries[0].id, "cl-0");
+        let tagged = HistoryQuery {
+            tags: vec!["duo".into()],
+            min_score: Some(50.0),
+            ..Default::default()
+        };
+        assert_eq!(query_in(&conn, &tagged).unwrap().total, 1);
+        let jungle = HistoryQuery {
+            position: Some("JUNGLE".into()),
+            ..Default::default()
+        };
+        assert_eq!(query_in(&conn, &jungle).unwrap().entries[0].id, "cl-1");
+
+        conn.execute(
+            "INSERT INTO checklists (id, saved_at_ms, data, champion_id) VALUES ('cl-9', 9, '{}', 22)",
+            [],
+        )
+        .unwrap();
+        let ashe = HistoryQuery {
+            champion_id: Some(22),
+            ..Default::default()
+        };
+        let page = query_in(&conn, &ashe).unwrap();
+        assert_eq!((page.total, page.entries.len()), (3, 3));
+    }
+}
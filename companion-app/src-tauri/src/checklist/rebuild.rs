@@ -0,0 +1,163 @@
+//! Rebuild stats and checklists of archived games with the current parsers.
+
+use super::evaluator::{checklist_for_game, reevaluate};
+use super::rules::load_rules;
+use super::store::{find_in, insert_if_missing, load_all_in, upsert};
+use super::SavedChecklist;
+use crate::app_config::load_companion_config;
+use crate::db::{self, sql_err};
+use crate::postgame::{archive, match_history, PostGameStats};
+use rusqlite::Connection;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RebuildSummary {
+    pub archived: u32,
+    pub updated: u32,
+    pub added: u32,
+    pub skipped: u32,
+    pub failed: u32,
+    /// Entries edited while the rebuild ran; left as they were.
+    pub stale: u32,
+}
+
+/// Keep what the raw payloads cannot provide (live CS samples, timeline frames, rank).
+fn carry_over(previous: &PostGameStats, stats: &mut PostGameStats) {
+    for (&minute, &cs) in &previous.cs_milestones {
+        stats.cs_milestones.entry(minute).or_insert(cs);
+    }
+    if stats.minute_frames.is_empty() {
+        stats.minute_frames = previous.minute_frames.clone();
+    }
+    if stats.player_rank.is_empty() {
+        stats.player_rank = previous.player_rank.clone();
+        stats.player_lp = previous.player_lp;
+    }
+}
+
+/// Write the rebuilt entries in one transaction. Updates only apply to entries still stored as
+/// they were read; added entries get a free id and are dropped when their game was saved meanwhile.
+pub(crate) fn store_in(
+    conn: &mut Connection,
+    updates: &[(SavedChecklist, SavedChecklist)],
+    added: Vec<SavedChecklist>,
+    summary: &mut RebuildSummary,
+) -> Result<(), String> {
+    let tx = conn.transaction().map_err(sql_err)?;
+    for (old, new) in updates {
+        let stored = find_in(&tx, &old.id, None).ok().flatten();
+        if stored.is_some_and(|s| serde_json::to_value(s).ok() == serde_json::to_value(old).ok()) {
+            upsert(&tx, new)?;
+            summary.updated += 1;
+        } else {
+            summary.stale += 1;
+        }
+    }
+    for mut entry in added {
+        let mut ms = entry.saved_at_ms;
+        while !matches!(find_in(&tx, &entry.id, None), Ok(None)) {
+            ms += 1;
+            entry.id = format!("cl-{ms}");
+        }
+        if insert_if_missing(&tx, &entry)? {
+            summary.added += 1;
+        } else {
+            summary.skipped += 1;
+        }
+    }
+    tx.commit().map_err(sql_err)
+}
+
+/// Re-parse every archived game; existing entries keep their id, notes and user edits.
+pub fn rebuild_from_archive() -> Result<(Vec<SavedChecklist>, RebuildSummary), String> {
+    let settings = load_companion_config().game_scoring;
+    let rules = load_rules();
+    let mut conn = db::connect()?;
+    let entries = load_all_in(&conn)?;
+    let (mut updates, mut added) = (Vec::new(), Vec::new());
+    let mut summary = RebuildSummary::default();
+    for game_id in archive::list() {
+        summary.archived += 1;
+        let rebuilt =
+            archive::load(game_id).and_then(|a| archive::rebuild_stats(&a).map(|stats| (a, stats)));
+        let Ok((archived, mut stats)) = rebuilt else {
+            summary.failed += 1;
+            continue;
+        };
+        if let Some(entry) = entries.iter().find(|e| e.stats.game_id == Some(game_id)) {
+            carry_over(&entry.stats, &mut stats);
+            let mut next = entry.clone();
+            next.stats = stats;
+            updates.push((entry.clone(), reevaluate(&next)));
+            continue;
+        }
+        let Some(mut entry) = checklist_for_game(stats, true, &settings, &rules) else {
+            summary.skipped += 1;
+            continue;
+        };
+        let ended_at_ms = archived
+            .game
+            .as_ref()
+            .map(match_history::game_end_ms)
+            .filter(|&ms| ms > 0)
+            .unwrap_or(archived.archived_at_ms);
+        entry.saved_at_ms = ended_at_ms;
+        entry.id = format!("cl-{ended_at_ms}");
+        added.push(entry);
+    }
+    store_in(&mut conn, &updates, added, &mut summary)?;
+    Ok((load_all_in(&conn)?, summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+
+    #[test]
+    fn keeps_entries_saved_or_edited_during_the_rebuild() {
+        let mut conn = db::test_conn();
+        let mut edited = test_checklist(PostGameStats {
+            game_id: Some(1),
+            ..Default::default()
+        });
+        edited.id = "cl-1".into();
+        upsert(&conn, &edited).unwrap();
+        let mut taken = test_checklist(PostGameStats {
+            game_id: Some(2),
+            ..Default::default()
+        });
+        taken.id = "cl-5".into();
+        taken.notes = "saved meanwhile".into();
+        upsert(&conn, &taken).unwrap();
+
+        let mut rebuilt = edited.clone();
+        rebuilt.notes = "rebuilt".into();
+        let mut stale_copy = edited.clone();
+        stale_copy.notes = "read before the edit".into();
+        let mut new_game = test_checklist(PostGameStats {
+            game_id: Some(3),
+            ..Default::default()
+        });
+        new_game.id = "cl-5".into();
+        new_game.saved_at_ms = 5;
+
+        let mut summary = RebuildSummary::default();
+        store_in(
+            &mut conn,
+            &[(stale_copy, rebuilt)],
+            vec![new_game],
+            &mut summary,
+        )
+        .unwrap();
+        assert_eq!((summary.stale, summary.updated, summary.added), (1, 0, 1));
+        assert_eq!(find_in(&conn, "cl-1", None).unwrap().unwrap().notes, "");
+        assert_eq!(
+            find_in(&conn, "cl-5", None).unwrap().unwrap().notes,
+            "saved meanwhile"
+        );
+        let added = find_in(&conn, "cl-6", None).unwrap().unwrap();
+        assert_eq!(added.stats.game_id, Some(3));
+    }
+}
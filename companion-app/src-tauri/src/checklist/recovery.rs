@@ -0,0 +1,140 @@
+//! Live draft and CS samples saved during the game, restored after a crash or restart.
+//!
+//! The timeline is not saved: the Live Client event feed replays every event of the game.
+
+use super::evaluator::{now_millis, SavedChecklist};
+use crate::db::{self, sql_err};
+use crate::live_client::CsSample;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+/// Older saves belong to a game that cannot still be running.
+const STALE_AFTER_MS: i64 = 3 * 60 * 60 * 1000;
+
+/// Phases in which the saved game may still be running or waiting for its end-of-game stats.
+const RESUMABLE_PHASES: &[&str] = &[
+    "InProgress",
+    "Reconnect",
+    "WaitingForStats",
+    "PreEndOfGame",
+    "EndOfGame",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveRecovery {
+    pub game_id: Option<u64>,
+    pub saved_at_ms: i64,
+    #[serde(default)]
+    pub draft: Option<SavedChecklist>,
+    #[serde(default)]
+    pub cs_samples: Vec<CsSample>,
+}
+
+impl LiveRecovery {
+    /// Same game, still in a phase where it matters, and saved recently enough.
+    pub fn is_resumable(&self, game_id: Option<u64>, phase: &str, now_ms: i64) -> bool {
+        RESUMABLE_PHASES.contains(&phase)
+            && self.game_id.is_some()
+            && self.game_id == game_id
+            && now_ms - self.saved_at_ms < STALE_AFTER_MS
+    }
+}
+
+pub(crate) fn save_in(conn: &Connection, recovery: &LiveRecovery) -> Result<(), String> {
+    let data = serde_json::to_string(recovery).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO live_draft (id, data) VALUES (1, ?1)
+         ON CONFLICT (id) DO UPDATE SET data = excluded.data",
+        [data],
+    )
+    .map(|_| ())
+    .map_err(sql_err)
+}
+
+/// `None` when nothing is saved or the save cannot be decoded.
+pub(crate) fn load_in(conn: &Connection) -> Result<Option<LiveRecovery>, String> {
+    let raw: Option<String> = conn
+        .query_row("SELECT data FROM live_draft WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(sql_err)?;
+    Ok(raw.and_then(|raw| serde_json::from_str(&raw).ok()))
+}
+
+pub(crate) fn clear_in(conn: &Connection) -> Result<(), String> {
+    conn.execute("DELETE FROM live_draft", [])
+        .map(|_| ())
+        .map_err(sql_err)
+}
+
+/// Runs every 30 s during the game.
+pub fn save(
+    game_id: Option<u64>,
+    draft: Option<SavedChecklist>,
+    cs_samples: Vec<CsSample>,
+) -> Result<(), String> {
+    let recovery = LiveRecovery {
+        game_id,
+        saved_at_ms: now_millis(),
+        draft,
+        cs_samples,
+    };
+    save_in(&db::connect()?, &recovery)
+}
+
+/// The saved state when it belongs to the game in `phase`; anything else is discarded.
+pub fn take_resumable(game_id: Option<u64>, phase: &str) -> Result<Option<LiveRecovery>, String> {
+    let conn = db::connect()?;
+    let Some(recovery) = load_in(&conn)? else {
+        return Ok(None);
+    };
+    if recovery.is_resumable(game_id, phase, now_millis()) {
+        Ok(Some(recovery))
+    } else {
+        clear_in(&conn)?;
+        Ok(None)
+    }
+}
+
+pub fn clear() -> Result<(), String> {
+    clear_in(&db::connect()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+
+    #[test]
+    fn saves_and_checks_the_game() {
+        let conn = db::test_conn();
+        assert!(load_in(&conn).unwrap().is_none());
+
+        let mut draft = test_checklist(Default::default());
+        draft.notes = "ward river at 3:00".into();
+        let recovery = LiveRecovery {
+            game_id: Some(7),
+            saved_at_ms: 1_000,
+            draft: Some(draft),
+            cs_samples: vec![CsSample {
+                game_time: 300.0,
+                cs: 41,
+            }],
+        };
+        save_in(&conn, &recovery).unwrap();
+        let loaded = load_in(&conn).unwrap().unwrap();
+        assert_eq!(loaded.cs_samples, recovery.cs_samples);
+        assert_eq!(loaded.draft.unwrap().notes, "ward river at 3:00");
+
+        assert!(recovery.is_resumable(Some(7), "InProgress", 60_000));
+        assert!(recovery.is_resumable(Some(7), "EndOfGame", 60_000));
+        assert!(!recovery.is_resumable(Some(8), "InProgress", 60_000));
+        assert!(!recovery.is_resumable(Some(7), "Lobby", 60_000));
+        assert!(!recovery.is_resumable(Some(7), "InProgress", STALE_AFTER_MS + 1_000));
+
+        clear_in(&conn).unwrap();
+        assert!(load_in(&conn).unwrap().is_none());
+    }
+}
@@ -0,0 +1,233 @@
+//! Re-evaluate stored games with the current evaluator and rules (or only re-weight them),
+//! previewing score changes before writing exactly what was previewed.
+
+use super::evaluator::{
+    effective_kind, reevaluate_with, rescore, SavedChecklist, EVALUATOR_VERSION,
+};
+use super::rules::ChecklistRules;
+use super::store::{find_in, load_all_in, upsert};
+use crate::app_config::GameScoringSettings;
+use crate::db::{self, sql_err};
+use rusqlite::Connection;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreChange {
+    pub id: String,
+    pub saved_at_ms: i64,
+    pub game_id: Option<u64>,
+    pub old_score: f32,
+    pub new_score: f32,
+    /// Items whose result differs (user overrides are kept, so those never change).
+    pub changed_items: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RescorePreview {
+    pub total: u32,
+    /// Entries evaluated with another evaluator version or rules version.
+    pub outdated: u32,
+    /// Entries whose score or items change, in history order.
+    pub changes: Vec<ScoreChange>,
+    /// Averages over scored entries.
+    pub avg_score_before: f32,
+    pub avg_score_after: f32,
+    pub evaluator_version: u32,
+    pub ruleset_version: String,
+    /// Only the current weights were applied; items were not re-evaluated.
+    pub weights_only: bool,
+    /// False for a preview.
+    pub applied: bool,
+    /// Entries edited or deleted since the preview, left as they are when applying.
+    pub stale: u32,
+}
+
+/// A preview and the entries it would write.
+#[derive(Debug, Clone)]
+pub struct Reevaluation {
+    pub preview: RescorePreview,
+    /// Stored entry and its new version, for every entry that differs.
+    updates: Vec<(SavedChecklist, SavedChecklist)>,
+}
+
+pub fn is_outdated(entry: &SavedChecklist, rules: &ChecklistRules) -> bool {
+    entry.evaluator_version != EVALUATOR_VERSION || entry.ruleset_version != rules.version
+}
+
+fn avg_score(entries: &[SavedChecklist]) -> f32 {
+    let scores: Vec<f32> = entries
+        .iter()
+        .filter(|e| !e.unscored)
+        .map(|e| e.score)
+        .collect();
+    scores.iter().sum::<f32>() / scores.len().max(1) as f32
+}
+
+/// The history re-evaluated from stored stats and reclassified under `settings` (user edits
+/// kept), or only re-weighted with `weights_only`, and what would change.
+pub fn preview(
+    history: &[SavedChecklist],
+    rules: &ChecklistRules,
+    settings: &GameScoringSettings,
+    weights_only: bool,
+) -> Reevaluation {
+    let next: Vec<SavedChecklist> = history
+        .iter()
+        .map(|entry| {
+            if weights_only {
+                let mut next = entry.clone();
+                rescore(&mut next, &rules.weights);
+                next
+            } else {
+                reevaluate_with(entry, rules, settings)
+            }
+        })
+        .collect();
+    let changes = history
+        .iter()
+        .zip(&next)
+        .filter_map(|(old, new)| {
+            let changed_items: Vec<String> = new
+                .items
+                .iter()
+                .filter(|item| {
+                    old.items.iter().find(|o| o.id == item.id).is_none_or(|o| {
+                        effective_kind(o, old.unscored) != effective_kind(item, new.unscored)
+                    })
+                })
+                .map(|item| item.id.clone())
+                .collect();
+            let score_changed = (old.score - new.score).abs() >= 0.05;
+            (score_changed || !changed_items.is_empty()).then(|| ScoreChange {
+                id: old.id.clone(),
+                saved_at_ms: old.saved_at_ms,
+                game_id: old.stats.game_id,
+                old_score: old.score,
+                new_score: new.score,
+                changed_items,
+            })
+        })
+        .collect();
+    let preview = RescorePreview {
+        total: history.len() as u32,
+        outdated: history.iter().filter(|e| is_outdated(e, rules)).count() as u32,
+        changes,
+        avg_score_before: avg_score(history),
+        avg_score_after: avg_score(&next),
+        evaluator_version: EVALUATOR_VERSION,
+        ruleset_version: rules.version.clone(),
+        weights_only,
+        applied: false,
+        stale: 0,
+    };
+    let updates = history
+        .iter()
+        .cloned()
+        .zip(next)
+        .filter(|(old, new)| serde_json::to_value(old).ok() != serde_json::to_value(new).ok())
+        .collect();
+    Reevaluation { preview, updates }
+}
+
+/// Write the previewed entries in one transaction, skipping those that changed since.
+pub(crate) fn apply_in(
+    conn: &mut Connection,
+    reevaluation: &Reevaluation,
+) -> Result<RescorePreview, String> {
+    let mut preview = reevaluation.preview.clone();
+    let tx = conn.transaction().map_err(sql_err)?;
+    for (old, new) in &reevaluation.updates {
+        let stored = find_in(&tx, &old.id, None).ok().flatten();
+        if stored.is_some_and(|s| serde_json::to_value(s).ok() == serde_json::to_value(old).ok()) {
+            upsert(&tx, new)?;
+        } else {
+            preview.stale += 1;
+        }
+    }
+    tx.commit().map_err(sql_err)?;
+    preview.applied = true;
+    Ok(preview)
+}
+
+/// Apply a preview; returns the outcome and the new history.
+pub fn apply(reevaluation: &Reevaluation) -> Result<(RescorePreview, Vec<SavedChecklist>), String> {
+    let mut conn = db::connect()?;
+    let preview = apply_in(&mut conn, reevaluation)?;
+    Ok((preview, load_all_in(&conn)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_config::ScoringAction;
+    use crate::checklist::evaluator::{to_saved_checklist_with, CheckResultKind};
+    use crate::postgame::PostGameStats;
+
+    #[test]
+    fn previews_changes_and_keeps_user_edits() {
+        let rules = ChecklistRules::default();
+        let stats = PostGameStats {
+            game_duration_seconds: 1800,
+            deaths: 1,
+            ..Default::default()
+        };
+        let current = to_saved_checklist_with(stats.clone(), true, &rules);
+
+        // Saved by an older release that failed the game on deaths.
+        let mut old = to_saved_checklist_with(stats, true, &rules);
+        old.id = "cl-old".into();
+        old.evaluator_version = 0;
+        old.notes = "tilted after first death".into();
+        let survival = old.items.iter_mut().find(|i| i.id == "survival").unwrap();
+        survival.kind = CheckResultKind::Failed;
+        let overridden = old.items.iter_mut().find(|i| i.id == "pink_wards").unwrap();
+        overridden.user_kind = Some(CheckResultKind::Checked);
+        old.score -= 10.0;
+
+        let mut conn = db::test_conn();
+        upsert(&conn, &old).unwrap();
+        upsert(&conn, &current).unwrap();
+        let settings = GameScoringSettings::default();
+        let history = [old.clone(), current.clone()];
+        let reevaluation = preview(&history, &rules, &settings, false);
+        let summary = &reevaluation.preview;
+        assert_eq!((summary.total, summary.outdated), (2, 1));
+        assert_eq!(summary.changes.len(), 1);
+        assert_eq!(summary.changes[0].changed_items, vec!["survival"]);
+        assert!(summary.avg_score_after > summary.avg_score_before);
+        assert!(!summary.applied);
+        assert_eq!(reevaluation.updates.len(), 1);
+
+        let next = &reevaluation.updates[0].1;
+        assert_eq!(next.evaluator_version, EVALUATOR_VERSION);
+        assert_eq!(next.notes, "tilted after first death");
+        let pink = next.items.iter().find(|i| i.id == "pink_wards").unwrap();
+        assert_eq!(pink.user_kind, Some(CheckResultKind::Checked));
+
+        // Applying writes the previewed entry once; afterwards it no longer matches.
+        let applied = apply_in(&mut conn, &reevaluation).unwrap();
+        assert!(applied.applied);
+        assert_eq!(applied.stale, 0);
+        let stored = find_in(&conn, "cl-old", None).unwrap().unwrap();
+        assert_eq!(stored.score, next.score);
+        assert_eq!(apply_in(&mut conn, &reevaluation).unwrap().stale, 1);
+
+        let mut aram = current.clone();
+        aram.stats.game_mode = "ARAM".into();
+        let aram_unscored = GameScoringSettings {
+            aram: ScoringAction::Unscored,
+            ..settings.clone()
+        };
+        let reclassified = preview(&[aram], &rules, &aram_unscored, false);
+        assert!(reclassified.updates[0].1.unscored);
+
+        let mut reweighted = rules.clone();
+        reweighted.weights.items.insert("survival".into(), 0.0);
+        let weights_only = preview(&[old], &reweighted, &settings, true).preview;
+        assert!(weights_only.weights_only);
+        assert!(weights_only.changes[0].changed_items.is_empty());
+        assert!(weights_only.changes[0].new_score > weights_only.changes[0].old_score);
+    }
+}
@@ -0,0 +1,389 @@
+//! Checklist thresholds from `checklist_rules.json` in the data directory (bundled defaults).
+
+use super::evaluator::{is_measurable, CUSTOM_PREFIX};
+use super::expr::{self, Variables};
+use crate::db::data_dir;
+use crate::postgame::game_kind::GameMode;
+use crate::postgame::role::Role;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+const DEFAULT_RULES: &str = include_str!("default_rules.json");
+
+const ROLE_KEYS: &[&str] = &["top", "jungle", "middle", "bottom", "support"];
+const MODE_KEYS: &[&str] = &["summonersRift", "aram", "arena", "other"];
+const TIER_KEYS: &[&str] = &[
+    "IRON",
+    "BRONZE",
+    "SILVER",
+    "GOLD",
+    "PLATINUM",
+    "EMERALD",
+    "DIAMOND",
+    "MASTER",
+    "GRANDMASTER",
+    "CHALLENGER",
+];
+
+/// `checked` and above is checked, `partial` and above partial (deaths: at most).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct Threshold {
+    pub checked: f32,
+    pub partial: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Thresholds {
+    pub cs_at_5: Threshold,
+    pub cs_at_10: Threshold,
+    pub cs_per_min: Threshold,
+    pub pink_wards: Threshold,
+    pub wards_per_min: Threshold,
+    pub vision_denied: Threshold,
+    /// Maximum deaths (lower is better).
+    pub deaths: Threshold,
+    /// Dragons + barons taken by the team.
+    pub team_objectives: Threshold,
+    pub vision_score_per_min: Threshold,
+    /// Share of the team's dragons / barons taken by the player (0–1).
+    pub objective_participation: Threshold,
+}
+
+/// How much each measurable item counts in the checklist score.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoreWeights {
+    /// Item id → weight; items not listed weigh 1.
+    #[serde(default)]
+    pub items: BTreeMap<String, f32>,
+    /// Share of an item's weight earned when it is partial.
+    #[serde(default = "default_partial_credit")]
+    pub partial_credit: f32,
+}
+
+fn default_partial_credit() -> f32 {
+    0.5
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            items: BTreeMap::new(),
+            partial_credit: default_partial_credit(),
+        }
+    }
+}
+
+impl ScoreWeights {
+    pub fn weight(&self, id: &str) -> f32 {
+        self.items.get(id).copied().unwrap_or(1.0)
+    }
+}
+
+/// User-defined item scored from an expression over the game stats.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomItem {
+    /// Letters, digits and `_`; the checklist item id is `custom:<id>`.
+    pub id: String,
+    pub label: String,
+    /// e.g. `kills + assists >= 10` or `gold_earned / minutes`.
+    pub expression: String,
+    /// Without thresholds a non-zero result is checked and zero failed.
+    #[serde(default)]
+    pub threshold: Option<Threshold>,
+    #[serde(default)]
+    pub lower_is_better: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistRules {
+    /// Recorded on every saved checklist (e.g. `default-1`, `coach-gold-2`).
+    pub version: String,
+    pub thresholds: Thresholds,
+    /// Partial overrides per game mode (`aram`, `arena`, …), applied first.
+    #[serde(default)]
+    pub modes: BTreeMap<String, Value>,
+    /// Partial overrides per role (`top`, `jungle`, `middle`, `bottom`, `support`).
+    #[serde(default)]
+    pub roles: BTreeMap<String, Value>,
+    /// Partial overrides per tier (`IRON` … `CHALLENGER`), applied last.
+    #[serde(default)]
+    pub ranks: BTreeMap<String, Value>,
+    /// Added to every new checklist after the built-in items.
+    #[serde(default)]
+    pub custom_items: Vec<CustomItem>,
+    /// Copied onto every new checklist so its score stays comparable.
+    #[serde(default)]
+    pub weights: ScoreWeights,
+}
+
+impl Default for ChecklistRules {
+    fn default() -> Self {
+        serde_json::from_str(DEFAULT_RULES).expect("bundled default_rules.json is valid")
+    }
+}
+
+fn merge(base: &mut Value, over: &Value) {
+    match (base, over) {
+        (Value::Object(base), Value::Object(over)) => {
+            for (key, value) in over {
+                match base.get_mut(key) {
+                    Some(slot) => merge(slot, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, over) => *base = over.clone(),
+    }
+}
+
+fn mode_key(mode: GameMode) -> Option<String> {
+    serde_json::to_value(mode)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+}
+
+fn role_key(role: Role) -> Option<String> {
+    match role {
+        Role::Unknown => None,
+        role => serde_json::to_value(role)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string)),
+    }
+}
+
+/// `Gold II` → `GOLD`.
+fn tier_key(player_rank: &str) -> Option<String> {
+    let tier = player_rank.split_whitespace().next()?.to_ascii_uppercase();
+    TIER_KEYS.contains(&tier.as_str()).then_some(tier)
+}
+
+fn apply_overrides(base: &Thresholds, overrides: &[&Value]) -> Result<Thresholds, String> {
+    let mut merged = serde_json::to_value(base).map_err(|e| e.to_string())?;
+    for over in overrides {
+        merge(&mut merged, over);
+    }
+    serde_json::from_value(merged).map_err(|e| e.to_string())
+}
+
+impl ChecklistRules {
+    /// Thresholds for a game: base, then mode, role and rank overrides.
+    pub fn resolve(&self, mode: GameMode, role: Role, player_rank: &str) -> Thresholds {
+        let mut overrides = Vec::new();
+        if let Some(v) = mode_key(mode).and_then(|k| self.modes.get(&k)) {
+            overrides.push(v);
+        }
+        if let Some(v) = role_key(role).and_then(|k| self.roles.get(&k)) {
+            overrides.push(v);
+        }
+        if let Some(v) = tier_key(player_rank).and_then(|k| self.ranks.get(&k)) {
+            overrides.push(v);
+        }
+        apply_overrides(&self.thresholds, &overrides).unwrap_or_else(|_| self.thresholds.clone())
+    }
+
+    /// Every problem found, or `Ok` when the rules can be used as-is.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if self.version.trim().is_empty() {
+            errors.push("version must not be empty".to_string());
+        }
+        check_thresholds("thresholds", &self.thresholds, &mut errors);
+        let groups: [(&str, &BTreeMap<String, Value>, &[&str]); 3] = [
+            ("modes", &self.modes, MODE_KEYS),
+            ("roles", &self.roles, ROLE_KEYS),
+            ("ranks", &self.ranks, TIER_KEYS),
+        ];
+        for (group, overrides, keys) in groups {
+            for (key, over) in overrides {
+                let path = format!("{group}.{key}");
+                if !keys.contains(&key.as_str()) {
+                    errors.push(format!(
+                        "{path}: unknown key (expected one of {})",
+                        keys.join(", ")
+                    ));
+                    continue;
+                }
+                match apply_overrides(&self.thresholds, &[over]) {
+                    Ok(t) => check_thresholds(&path, &t, &mut errors),
+                    Err(e) => errors.push(format!("{path}: {e}")),
+                }
+            }
+        }
+        let mut ids = Vec::new();
+        for custom in &self.custom_items {
+            let path = format!("customItems.{}", custom.id);
+            if custom.id.is_empty()
+                || !custom
+                    .id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                errors.push(format!("{path}: id must be letters, digits or `_`"));
+            } else if ids.contains(&custom.id) {
+                errors.push(format!("{path}: duplicate id"));
+            }
+            ids.push(custom.id.clone());
+            if custom.label.trim().is_empty() {
+                errors.push(format!("{path}: label must not be empty"));
+            }
+            match expr::parse(&custom.expression) {
+                Ok(parsed) => {
+                    let mut names = Vec::new();
+                    expr::variables(&parsed, &mut names);
+                    for name in names.iter().filter(|n| !Variables::is_known(n)) {
+                        errors.push(format!("{path}: unknown variable `{name}`"));
+                    }
+                }
+                Err(e) => errors.push(format!("{path}: {e}")),
+            }
+            if let Some(th) = custom.threshold {
+                let ordered = if custom.lower_is_better {
+                    th.partial >= th.checked
+                } else {
+                    th.partial <= th.checked
+                };
+                if !th.checked.is_finite() || !th.partial.is_finite() || !ordered {
+                    errors.push(format!("{path}: partial must not be stricter than checked"));
+                }
+            }
+        }
+        let partial_credit = self.weights.partial_credit;
+        if !(0.0..=1.0).contains(&partial_credit) {
+            errors.push("weights.partialCredit: must be between 0 and 1".to_string());
+        }
+        for (id, weight) in &self.weights.items {
+            let known = match id.strip_prefix(CUSTOM_PREFIX) {
+                Some(custom) => ids.iter().any(|c| c == custom),
+                None => is_measurable(id),
+            };
+            if !known {
+                errors.push(format!("weights.items.{id}: not a measurable item"));
+            }
+            if !weight.is_finite() || *weight < 0.0 {
+                errors.push(format!("weights.items.{id}: must be a non-negative number"));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
+
+fn check_thresholds(path: &str, t: &Thresholds, errors: &mut Vec<String>) {
+    let higher_is_better = [
+        ("csAt5", t.cs_at_5),
+        ("csAt10", t.cs_at_10),
+        ("csPerMin", t.cs_per_min),
+        ("pinkWards", t.pink_wards),
+        ("wardsPerMin", t.wards_per_min),
+        ("visionDenied", t.vision_denied),
+        ("teamObjectives", t.team_objectives),
+        ("visionScorePerMin", t.vision_score_per_min),
+        ("objectiveParticipation", t.objective_participation),
+    ];
+    let deaths = [("deaths", t.deaths)];
+    for (name, th) in higher_is_better.iter().chain(&deaths) {
+        if !th.checked.is_finite()
+            || !th.partial.is_finite()
+            || th.checked < 0.0
+            || th.partial < 0.0
+        {
+            errors.push(format!(
+                "{path}.{name}: values must be non-negative numbers"
+            ));
+        }
+    }
+    for (name, th) in higher_is_better {
+        if th.partial > th.checked {
+            errors.push(format!("{path}.{name}: partial must not exceed checked"));
+        }
+    }
+    if t.deaths.partial < t.deaths.checked {
+        errors.push(format!("{path}.deaths: partial must be at least checked"));
+    }
+}
+
+fn rules_path() -> PathBuf {
+    data_dir().join("checklist_rules.json")
+}
+
+/// Rules from disk; an error when the file exists but is unreadable or invalid.
+pub fn load_rules_checked() -> Result<ChecklistRules, String> {
+    let raw = match std::fs::read_to_string(rules_path()) {
+        Ok(raw) => raw,
+        Err(_) => return Ok(ChecklistRules::default()),
+    };
+    let rules: ChecklistRules =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid checklist_rules.json: {e}"))?;
+    rules.validate()?;
+    Ok(rules)
+}
+
+/// Rules used for evaluation (bundled defaults when the file is missing or invalid).
+pub fn load_rules() -> ChecklistRules {
+    load_rules_checked().unwrap_or_default()
+}
+
+pub fn save_rules(rules: &ChecklistRules) -> Result<(), String> {
+    rules.validate()?;
+    std::fs::create_dir_all(data_dir()).map_err(|e| e.to_string())?;
+    let raw = serde_json::to_string_pretty(rules).map_err(|e| e.to_string())?;
+    std::fs::write(rules_path(), raw).map_err(|e| e.to_string())
+}
+
+/// Delete the user file so the bundled defaults apply again.
+pub fn reset_rules() -> ChecklistRules {
+    let _ = std::fs::remove_file(rules_path());
+    ChecklistRules::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_defaults_are_valid_and_layered() {
+        let rules = ChecklistRules::default();
+        assert!(rules.validate().is_ok());
+        let laner = rules.resolve(GameMode::SummonersRift, Role::Middle, "Gold II");
+        assert_eq!(laner.cs_at_10.checked, 85.0);
+        let jungle = rules.resolve(GameMode::SummonersRift, Role::Jungle, "");
+        assert_eq!(jungle.cs_at_10.checked, 62.0);
+        assert_eq!(jungle.cs_at_10.partial, 52.0);
+        let aram = rules.resolve(GameMode::Aram, Role::Unknown, "");
+        assert_eq!(aram.deaths.partial, 8.0);
+    }
+
+    #[test]
+    fn rank_overrides_apply_and_bad_rules_are_rejected() {
+        let mut rules = ChecklistRules::default();
+        rules.ranks.insert(
+            "IRON".into(),
+            serde_json::json!({ "csPerMin": { "checked": 6.0 } }),
+        );
+        let iron = rules.resolve(GameMode::SummonersRift, Role::Top, "Iron IV");
+        assert_eq!(iron.cs_per_min.checked, 6.0);
+        assert_eq!(iron.cs_per_min.partial, 6.5);
+        assert!(rules.validate().is_err());
+
+        rules.ranks.clear();
+        rules.roles.insert("adc".into(), serde_json::json!({}));
+        rules.thresholds.deaths = Threshold {
+            checked: 4.0,
+            partial: 2.0,
+        };
+        let err = rules.validate().unwrap_err();
+        assert!(err.contains("roles.adc"));
+        assert!(err.contains("thresholds.deaths"));
+    }
+}
@@ -0,0 +1,227 @@
+//! Play sessions (games separated by less than a configurable gap) and tilt detection.
+
+use super::evaluator::SavedChecklist;
+use crate::app_config::SessionSettings;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaySession {
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+    /// Sum of the known LP changes.
+    pub lp_change: i32,
+    /// Scored games only.
+    pub avg_score: f32,
+    /// Score change per game (least squares over the session's scored games).
+    pub score_trend: f32,
+    pub avg_deaths: f32,
+    /// Oldest first.
+    pub checklist_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TiltSignal {
+    LossStreak,
+    FallingScore,
+    RisingDeaths,
+}
+
+/// Payload of `session:tilt-warning`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TiltWarning {
+    pub session_start_ms: i64,
+    pub games: u32,
+    pub signals: Vec<TiltSignal>,
+    pub loss_streak: u32,
+    /// First-half minus second-half average score, with at least four scored games.
+    pub score_drop: Option<f32>,
+    /// Second-half minus first-half average deaths, with at least four scored games.
+    pub deaths_rise: Option<f32>,
+    pub stop_playing: bool,
+}
+
+fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len().max(1) as f32
+}
+
+fn slope(values: &[f32]) -> f32 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let n = values.len() as f32;
+    let x_mean = (n - 1.0) / 2.0;
+    let y_mean = mean(values);
+    let (num, den) = values
+        .iter()
+        .enumerate()
+        .fold((0.0, 0.0), |(num, den), (x, y)| {
+            let dx = x as f32 - x_mean;
+            (num + dx * (y - y_mean), den + dx * dx)
+        });
+    num / den
+}
+
+/// Later half minus earlier half; `None` under four values.
+fn half_change(values: &[f32]) -> Option<f32> {
+    if values.len() < 4 {
+        return None;
+    }
+    let (first, second) = values.split_at(values.len() / 2);
+    Some(mean(second) - mean(first))
+}
+
+fn summarize(games: &[&SavedChecklist]) -> PlaySession {
+    let scored: Vec<&SavedChecklist> = games.iter().copied().filter(|g| !g.unscored).collect();
+    let scores: Vec<f32> = scored.iter().map(|g| g.score).collect();
+    let deaths: Vec<f32> = scored.iter().map(|g| g.stats.deaths as f32).collect();
+    let wins = games.iter().filter(|g| g.stats.win).count() as u32;
+    PlaySession {
+        start_ms: games.first().map_or(0, |g| g.saved_at_ms),
+        end_ms: games.last().map_or(0, |g| g.saved_at_ms),
+        games: games.len() as u32,
+        wins,
+        losses: games.len() as u32 - wins,
+        lp_change: games.iter().filter_map(|g| g.stats.lp_change).sum(),
+        avg_score: mean(&scores),
+        score_trend: slope(&scores),
+        avg_deaths: mean(&deaths),
+        checklist_ids: games.iter().map(|g| g.id.clone()).collect(),
+    }
+}
+
+fn group(history: &[SavedChecklist], gap_minutes: u32) -> Vec<Vec<&SavedChecklist>> {
+    let mut games: Vec<&SavedChecklist> = history.iter().collect();
+    games.sort_by_key(|g| g.saved_at_ms);
+    let gap_ms = i64::from(gap_minutes) * 60_000;
+    let mut sessions: Vec<Vec<&SavedChecklist>> = Vec::new();
+    for game in games {
+        match sessions.last_mut() {
+            Some(session)
+                if session
+                    .last()
+                    .is_some_and(|prev| game.saved_at_ms - prev.saved_at_ms <= gap_ms) =>
+            {
+                session.push(game)
+            }
+            _ => sessions.push(vec![game]),
+        }
+    }
+    sessions
+}
+
+/// Sessions, newest first.
+pub fn sessions(history: &[SavedChecklist], gap_minutes: u32) -> Vec<PlaySession> {
+    group(history, gap_minutes)
+        .iter()
+        .rev()
+        .map(|games| summarize(games))
+        .collect()
+}
+
+/// Tilt signals of the latest session; `None` when there are none or warnings are off.
+pub fn detect_tilt(history: &[SavedChecklist], settings: &SessionSettings) -> Option<TiltWarning> {
+    if !settings.tilt_warnings {
+        return None;
+    }
+    let sessions = group(history, settings.gap_minutes);
+    let games = sessions.last()?;
+    let scored: Vec<&SavedChecklist> = games.iter().copied().filter(|g| !g.unscored).collect();
+    let loss_streak = scored.iter().rev().take_while(|g| !g.stats.win).count() as u32;
+    let scores: Vec<f32> = scored.iter().map(|g| g.score).collect();
+    let deaths: Vec<f32> = scored.iter().map(|g| g.stats.deaths as f32).collect();
+    let score_drop = half_change(&scores).map(|change| -change);
+    let deaths_rise = half_change(&deaths);
+
+    let mut signals = Vec::new();
+    if settings.loss_streak > 0 && loss_streak >= settings.loss_streak {
+        signals.push(TiltSignal::LossStreak);
+    }
+    if score_drop.is_some_and(|drop| drop >= settings.score_drop) {
+        signals.push(TiltSignal::FallingScore);
+    }
+    if deaths_rise.is_some_and(|rise| rise >= settings.deaths_rise) {
+        signals.push(TiltSignal::RisingDeaths);
+    }
+    if signals.is_empty() {
+        return None;
+    }
+    Some(TiltWarning {
+        session_start_ms: games[0].saved_at_ms,
+        games: games.len() as u32,
+        stop_playing: settings.stop_after_signals > 0
+            && signals.len() as u32 >= settings.stop_after_signals,
+        signals,
+        loss_streak,
+        score_drop,
+        deaths_rise,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+    use crate::postgame::PostGameStats;
+
+    const MINUTE: i64 = 60_000;
+
+    fn game(saved_at_ms: i64, win: bool, deaths: u32, score: f32) -> SavedChecklist {
+        let stats = PostGameStats {
+            win,
+            deaths,
+            lp_change: Some(if win { 20 } else { -20 }),
+            ..Default::default()
+        };
+        let mut entry = test_checklist(stats);
+        entry.id = format!("cl-{saved_at_ms}");
+        entry.saved_at_ms = saved_at_ms;
+        entry.score = score;
+        entry
+    }
+
+    #[test]
+    fn groups_sessions_and_detects_tilt() {
+        let history = vec![
+            game(0, true, 2, 90.0),
+            game(200 * MINUTE, true, 1, 80.0),
+            game(240 * MINUTE, true, 2, 70.0),
+            game(280 * MINUTE, false, 6, 50.0),
+            game(320 * MINUTE, false, 7, 40.0),
+            game(360 * MINUTE, false, 8, 30.0),
+        ];
+        let all = sessions(&history, 45);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[1].games, 1);
+        let latest = &all[0];
+        assert_eq!((latest.games, latest.wins, latest.losses), (5, 2, 3));
+        assert_eq!(latest.lp_change, -20);
+        assert_eq!(latest.score_trend, -13.0);
+
+        let settings = SessionSettings::default();
+        let warning = detect_tilt(&history, &settings).unwrap();
+        assert_eq!(warning.session_start_ms, 200 * MINUTE);
+        assert_eq!(warning.loss_streak, 3);
+        assert_eq!(
+            warning.signals,
+            vec![
+                TiltSignal::LossStreak,
+                TiltSignal::FallingScore,
+                TiltSignal::RisingDeaths
+            ]
+        );
+        assert!(warning.stop_playing);
+
+        assert!(detect_tilt(&history[..3], &settings).is_none());
+        let off = SessionSettings {
+            tilt_warnings: false,
+            ..settings
+        };
+        assert!(detect_tilt(&history, &off).is_none());
+    }
+}
@@ -1,59 +1,170 @@
-//! Persist saved checklists to AppData (unlimited history).
+//! Saved checklists in the SQLite database (unlimited history).
 
 use super::evaluator::SavedChecklist;
-use std::path::PathBuf;
+use crate::db::{self, sql_err};
+use rusqlite::{params, Connection, OptionalExtension};
 
-fn data_dir() -> PathBuf {
-    #[cfg(target_os = "windows")]
-    {
-        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".into());
-        PathBuf::from(appdata).join("Lelanation").join("Companion")
-    }
-    #[cfg(not(target_os = "windows"))]
-    {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
-        PathBuf::from(home)
-            .join(".config")
-            .join("lelanation-companion")
+pub(crate) fn upsert(conn: &Connection, entry: &SavedChecklist) -> Result<(), String> {
+    let data = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO checklists
+            (id, saved_at_ms, game_id, data, champion_id, queue_id, win, ranked, score)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+         ON CONFLICT (id) DO UPDATE SET
+            saved_at_ms = excluded.saved_at_ms,
+            game_id = excluded.game_id,
+            data = excluded.data,
+            champion_id = excluded.champion_id,
+            queue_id = excluded.queue_id,
+            win = excluded.win,
+            ranked = excluded.ranked,
+            score = excluded.score",
+        params![
+            entry.id,
+            entry.saved_at_ms,
+            entry.stats.game_id.map(|g| g as i64),
+            data,
+            entry.stats.champion_id,
+            entry.stats.queue_id,
+            entry.stats.win,
+            entry.stats.ranked,
+            entry.score,
+        ],
+    )
+    .map(|_| ())
+    .map_err(sql_err)
+}
+
+/// Insert unless the id or `stats.game_id` is already stored; returns true when inserted.
+pub(crate) fn insert_if_missing(conn: &Connection, entry: &SavedChecklist) -> Result<bool, String> {
+    let known: bool = conn
+        .query_row(
+            "SELECT EXISTS (
+                SELECT 1 FROM checklists WHERE id = ?1 OR (?2 IS NOT NULL AND game_id = ?2)
+            )",
+            params![entry.id, entry.stats.game_id.map(|g| g as i64)],
+            |row| row.get(0),
+        )
+        .map_err(sql_err)?;
+    if known {
+        return Ok(false);
     }
+    upsert(conn, entry)?;
+    Ok(true)
 }
 
-fn checklists_path() -> PathBuf {
-    data_dir().join("checklists.json")
+/// Stored entry with this id, else the one for the same `game_id`; an error when that row
+/// cannot be decoded.
+pub(crate) fn find_in(
+    conn: &Connection,
+    id: &str,
+    game_id: Option<u64>,
+) -> Result<Option<SavedChecklist>, String> {
+    let row: Option<(String, String)> = conn
+        .query_row(
+            "SELECT id, data FROM checklists
+             WHERE id = ?1 OR (?2 IS NOT NULL AND game_id = ?2)
+             ORDER BY id = ?1 DESC LIMIT 1",
+            params![id, game_id.map(|g| g as i64)],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()
+        .map_err(sql_err)?;
+    row.map(|(stored_id, raw)| {
+        serde_json::from_str(&raw)
+            .map_err(|e| format!("Stored checklist {stored_id} cannot be read: {e}"))
+    })
+    .transpose()
 }
 
-fn write_all(entries: &[SavedChecklist]) {
-    if let Some(parent) = checklists_path().parent() {
-        let _ = std::fs::create_dir_all(parent);
-    }
-    if let Ok(json) = serde_json::to_string_pretty(entries) {
-        let _ = std::fs::write(checklists_path(), json);
+/// Newest first. Rows this version cannot decode are skipped but left in the database
+/// (counted in the storage status).
+pub(crate) fn load_all_in(conn: &Connection) -> Result<Vec<SavedChecklist>, String> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM checklists ORDER BY saved_at_ms DESC")
+        .map_err(sql_err)?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(sql_err)?;
+    let mut entries = Vec::new();
+    for raw in rows {
+        if let Ok(entry) = serde_json::from_str(&raw.map_err(sql_err)?) {
+            entries.push(entry);
+        }
     }
+    Ok(entries)
+}
+
+/// Stored entry with this id, without decoding the rest of the history.
+pub fn find(id: &str) -> Result<Option<SavedChecklist>, String> {
+    find_in(&db::connect()?, id, None)
+}
+
+pub fn load_all() -> Result<Vec<SavedChecklist>, String> {
+    load_all_in(&db::connect()?)
 }
 
-pub fn load_all() -> Vec<SavedChecklist> {
-    let path = checklists_path();
-    let raw = match std::fs::read_to_string(&path) {
-        Ok(s) => s,
-        Err(_) => return Vec::new(),
-    };
-    serde_json::from_str(&raw).unwrap_or_default()
+pub fn save_entry(entry: SavedChecklist) -> Result<Vec<SavedChecklist>, String> {
+    let conn = db::connect()?;
+    upsert(&conn, &entry)?;
+    load_all_in(&conn)
 }
 
-pub fn save_entry(entry: SavedChecklist) -> Vec<SavedChecklist> {
-    let mut all = load_all();
-    if let Some(idx) = all.iter().position(|e| e.id == entry.id) {
-        all[idx] = entry;
-    } else {
-        all.insert(0, entry);
+/// Add entries whose `stats.game_id` is not stored yet; returns the history and the added count.
+pub fn insert_missing(entries: Vec<SavedChecklist>) -> Result<(Vec<SavedChecklist>, u32), String> {
+    let mut conn = db::connect()?;
+    let tx = conn.transaction().map_err(sql_err)?;
+    let mut added = 0;
+    for entry in &entries {
+        if insert_if_missing(&tx, entry)? {
+            added += 1;
+        }
     }
-    write_all(&all);
-    all
+    tx.commit().map_err(sql_err)?;
+    Ok((load_all_in(&conn)?, added))
 }
 
-pub fn delete_entry(id: &str) -> Vec<SavedChecklist> {
-    let mut all = load_all();
-    all.retain(|e| e.id != id);
-    write_all(&all);
-    all
+pub fn delete_entry(id: &str) -> Result<Vec<SavedChecklist>, String> {
+    let conn = db::connect()?;
+    conn.execute("DELETE FROM checklists WHERE id = ?1", [id])
+        .map_err(sql_err)?;
+    load_all_in(&conn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+
+    #[test]
+    fn upserts_and_dedupes_by_game_id() {
+        let conn = db::test_conn();
+        let mut first = test_checklist(Default::default());
+        first.id = "cl-1".into();
+        first.saved_at_ms = 1;
+        first.stats.game_id = Some(42);
+        upsert(&conn, &first).unwrap();
+        first.notes = "edited".into();
+        upsert(&conn, &first).unwrap();
+
+        let mut same_game = first.clone();
+        same_game.id = "cl-2".into();
+        assert!(!insert_if_missing(&conn, &same_game).unwrap());
+        same_game.stats.game_id = Some(43);
+        same_game.saved_at_ms = 2;
+        assert!(insert_if_missing(&conn, &same_game).unwrap());
+
+        let all = load_all_in(&conn).unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].id, "cl-2");
+        assert_eq!(all[1].notes, "edited");
+
+        conn.execute(
+            "INSERT INTO checklists (id, saved_at_ms, data) VALUES ('cl-future', 3, '{}')",
+            [],
+        )
+        .unwrap();
+        assert!(find_in(&conn, "cl-future", None).is_err());
+        assert_eq!(load_all_in(&conn).unwrap().len(), 2);
+    }
 }
@@ -1,11 +1,15 @@
 //! Tauri commands for LCU build export.
 
 use crate::app_config::load_companion_config;
+use crate::checklist::rescore::{self, RescorePreview};
+use crate::checklist::rules::{self, ChecklistRules};
 use crate::checklist::{
-    delete_entry, load_all, recalculate_score, save_entry, to_saved_checklist, SavedChecklist,
+    delete_entry, find, load_all, merge_user_edits, recalculate_score, save_entry,
+    to_saved_checklist, SavedChecklist,
 };
 use crate::progression::{self, ProgressionSave};
-use crate::live_client;
+use crate::rank::{self, LpSeries, QueueRank};
+use crate::live_client::{self, TimelineEvent};
 use crate::lcu::{
     apply_item_set, apply_rune_page, apply_summoner_spells, fetch_gameflow_phase,
     resolve_champion_numeric_id, write_recommended_item_set, LcuClient,
@@ -155,7 +159,7 @@ pub fn apply_build(build: BuildPayload, state: State<'_, Arc<AppState>>) -> Resu
 }
 
 #[tauri::command]
-pub fn get_checklist_history() -> Vec<SavedChecklist> {
+pub fn get_checklist_history() -> Result<Vec<SavedChecklist>, String> {
     load_all()
 }
 
@@ -163,9 +167,19 @@ pub fn get_checklist_history() -> Vec<SavedChecklist> {
 pub fn get_checklist_draft(state: State<'_, Arc<AppState>>) -> Option<SavedChecklist> {
     let snap = state.live_cs_snapshot.lock().ok().map(|s| s.clone());
     if let Some(snap) = snap {
-        if let Some(stats) = live_client::fetch_live_stats(&snap) {
-            let draft = to_saved_checklist(stats, false);
+        let minutes = load_companion_config().cs_milestone_minutes();
+        if let Some(stats) = live_client::fetch_live_stats(&snap, &minutes) {
+            let mut draft = to_saved_checklist(stats, false);
+            draft.timeline = state
+                .live_timeline
+                .lock()
+                .map(|t| t.events.clone())
+                .unwrap_or_default();
             if let Ok(mut d) = state.checklist_draft.lock() {
+                if let Some(prev) = d.as_ref() {
+                    draft.id = prev.id.clone();
+                    merge_user_edits(&mut draft, prev);
+                }
                 *d = Some(draft.clone());
             }
             return Some(draft);
@@ -178,6 +192,39 @@ pub fn get_checklist_draft(state: State<'_, Arc<AppState>>) -> Option<SavedCheck
         .and_then(|d| d.clone())
 }
 
+/// Keep manual checks, overrides, notes and tags made on the live draft (saved for recovery).
+#[tauri::command]
+pub fn update_checklist_draft(
+    app: AppHandle,
+    edits: SavedChecklist,
+    state: State<'_, Arc<AppState>>,
+) -> Result<SavedChecklist, String> {
+    let draft = {
+        let mut guard = state.checklist_draft.lock().map_err(|e| e.to_string())?;
+        let draft = guard.as_mut().ok_or("No game in progress")?;
+        merge_user_edits(draft, &edits);
+        draft.clone()
+    };
+    crate::lcu::watcher::persist_live_state(&app, &state);
+    Ok(draft)
+}
+
+/// Timeline of a saved checklist, or of the live draft while the game is running.
+#[tauri::command]
+pub fn get_checklist_timeline(
+    id: String,
+    state: State<'_, Arc<AppState>>,
+) -> Result<Vec<TimelineEvent>, String> {
+    if let Some(entry) = find(&id)? {
+        return Ok(entry.timeline);
+    }
+    let draft = state.checklist_draft.lock().ok().and_then(|d| d.clone());
+    match draft {
+        Some(d) if d.id == id => Ok(d.timeline),
+        _ => Err(format!("Checklist '{id}' not found")),
+    }
+}
+
 #[tauri::command]
 pub fn save_checklist(mut entry: SavedChecklist) -> Result<Vec<SavedChecklist>, String> {
     if entry.id.is_empty() {
@@ -187,11 +234,12 @@ pub fn save_checklist(mut entry: SavedChecklist) -> Result<Vec<SavedChecklist>,
         entry.saved_at_ms = crate::checklist::evaluator::now_millis();
     }
     entry.auto_saved = false;
-    let (score, measured_count, checked_count) = recalculate_score(&entry.items);
+    let (score, measured_count, checked_count) =
+        recalculate_score(&entry.items, &entry.weights, entry.unscored);
     entry.score = score;
     entry.measured_count = measured_count;
     entry.checked_count = checked_count;
-    Ok(save_entry(entry))
+    save_entry(entry)
 }
 
 #[tauri::command]
@@ -199,56 +247,263 @@ pub fn update_checklist(mut entry: SavedChecklist) -> Result<Vec<SavedChecklist>
     if entry.id.is_empty() {
         return Err("Checklist id is required".into());
     }
-    let (score, measured_count, checked_count) = recalculate_score(&entry.items);
+    // Weights only change through `reevaluate_checklist_history`.
+    if let Some(existing) = find(&entry.id)? {
+        entry.weights = existing.weights;
+    }
+    let (score, measured_count, checked_count) =
+        recalculate_score(&entry.items, &entry.weights, entry.unscored);
     entry.score = score;
     entry.measured_count = measured_count;
     entry.checked_count = checked_count;
-    Ok(save_entry(entry))
+    save_entry(entry)
+}
+
+/// Import past games from LCU match history in the background (`checklist:backfill-progress`).
+#[tauri::command]
+pub fn backfill_checklist_history(
+    app: AppHandle,
+    max_games: Option<u32>,
+    max_age_days: Option<u32>,
+) -> Result<(), String> {
+    crate::checklist::backfill::start(app, max_games, max_age_days);
+    Ok(())
+}
+
+/// Fill missing CS milestones of a saved game from the LCU game timeline and re-evaluate it.
+#[tauri::command]
+pub fn refresh_checklist_from_timeline(id: String) -> Result<SavedChecklist, String> {
+    let entry = find(&id)?.ok_or_else(|| "Checklist not found".to_string())?;
+    let client = LcuClient::connect()?;
+    if !client.is_connected() {
+        return Err("League Client is not reachable".into());
+    }
+    let summoner_id = crate::postgame::local_summoner_id(&client)
+        .ok_or_else(|| "Cannot resolve summoner id".to_string())?;
+    let minutes = load_companion_config().cs_milestone_minutes();
+    let mut stats = entry.stats.clone();
+    crate::postgame::game_timeline::enrich_from_timeline(
+        &client,
+        summoner_id,
+        &mut stats,
+        &minutes,
+    )?;
+    let mut refreshed = entry.clone();
+    refreshed.stats = stats;
+    let refreshed = crate::checklist::evaluator::reevaluate(&refreshed);
+    save_entry(refreshed.clone())?;
+    Ok(refreshed)
+}
+
+/// Re-parse every archived game with the current parser and update the checklist history.
+#[tauri::command]
+pub fn rebuild_checklists_from_archive(
+    app: AppHandle,
+) -> Result<crate::checklist::rebuild::RebuildSummary, String> {
+    let (history, summary) = crate::checklist::rebuild::rebuild_from_archive()?;
+    let _ = app.emit("lcu:checklist-history", &history);
+    Ok(summary)
+}
+
+/// Current rank of every ranked queue (solo, flex, …) from the League Client.
+#[tauri::command]
+pub fn get_ranked_queues() -> Result<Vec<QueueRank>, String> {
+    let client = LcuClient::connect()?;
+    if !client.is_connected() {
+        return Err("League Client is not reachable".into());
+    }
+    let summoner_id = crate::postgame::local_summoner_id(&client)
+        .ok_or_else(|| "Cannot resolve summoner id".to_string())?;
+    rank::fetch_ranked_queues(&client, summoner_id)
+}
+
+/// LP history as chart series, one per queue (or only `queue_type`).
+#[tauri::command]
+pub fn get_lp_history(queue_type: Option<String>) -> Result<Vec<LpSeries>, String> {
+    Ok(rank::build_series(
+        &rank::store::load_history()?,
+        queue_type.as_deref(),
+    ))
+}
+
+/// Preview a re-evaluation of every stored game with the current evaluator, rules and game
+/// scoring settings, or with `weightsOnly` only the current weights. `apply` writes the last
+/// preview; user overrides, manual checks and notes are kept.
+#[tauri::command]
+pub fn reevaluate_checklist_history(
+    app: AppHandle,
+    state: State<'_, Arc<AppState>>,
+    weights_only: Option<bool>,
+    apply: Option<bool>,
+) -> Result<RescorePreview, String> {
+    let mut pending = state
+        .pending_reevaluation
+        .lock()
+        .map_err(|e| e.to_string())?;
+    if apply.unwrap_or(false) {
+        let reevaluation = pending
+            .take()
+            .ok_or("Nothing to apply: preview the re-evaluation first")?;
+        let (preview, history) = rescore::apply(&reevaluation)?;
+        let _ = app.emit("lcu:checklist-history", &history);
+        return Ok(preview);
+    }
+    let reevaluation = rescore::preview(
+        &load_all()?,
+        &rules::load_rules_checked()?,
+        &load_companion_config().game_scoring,
+        weights_only.unwrap_or(false),
+    );
+    let preview = reevaluation.preview.clone();
+    *pending = Some(reevaluation);
+    Ok(preview)
+}
+
+/// Checklist thresholds in use; an error when the user's rules file is invalid.
+#[tauri::command]
+pub fn get_checklist_rules() -> Result<ChecklistRules, String> {
+    rules::load_rules_checked()
 }
 
+/// Validate and save checklist thresholds (applies to games evaluated from now on).
 #[tauri::command]
-pub fn delete_checklist(id: String) -> Vec<SavedChecklist> {
+pub fn set_checklist_rules(rules: ChecklistRules) -> Result<ChecklistRules, String> {
+    rules::save_rules(&rules)?;
+    Ok(rules)
+}
+
+/// Drop the user's rules file and return the bundled defaults.
+#[tauri::command]
+pub fn reset_checklist_rules() -> ChecklistRules {
+    rules::reset_rules()
+}
+
+#[tauri::command]
+pub fn delete_checklist(id: String) -> Result<Vec<SavedChecklist>, String> {
     delete_entry(&id)
 }
 
+/// One page of the checklist history matching `query`, with the total match count.
+#[tauri::command]
+pub fn query_checklist_history(
+    query: crate::checklist::query::HistoryQuery,
+) -> Result<crate::checklist::query::HistoryPage, String> {
+    crate::checklist::query::query(&query)
+}
+
+/// Rolling averages (last 10 / 20 / 50 games by default), breakdowns and item pass rates.
+#[tauri::command]
+pub fn get_checklist_analytics(
+    windows: Option<Vec<u32>>,
+) -> Result<crate::checklist::analytics::ChecklistAnalytics, String> {
+    let windows = windows.unwrap_or_else(|| crate::checklist::analytics::DEFAULT_WINDOWS.to_vec());
+    Ok(crate::checklist::analytics::analyze(&load_all()?, &windows))
+}
+
+/// Write the selected games to `path` (picked with the save dialog); returns the exported count.
+#[tauri::command]
+pub fn export_checklist_history(
+    format: crate::checklist::export::ExportFormat,
+    path: String,
+    selection: Option<crate::checklist::export::ExportSelection>,
+) -> Result<u32, String> {
+    crate::checklist::export::export_to(
+        load_all()?,
+        &selection.unwrap_or_default(),
+        format,
+        std::path::Path::new(&path),
+    )
+}
+
+/// Merge a history file (JSON array or JSON Lines, picked with the open dialog) into the store.
+#[tauri::command]
+pub fn import_checklist_history(
+    app: AppHandle,
+    path: String,
+) -> Result<crate::checklist::import::ImportSummary, String> {
+    let (history, summary) = crate::checklist::import::import_from(std::path::Path::new(&path))?;
+    let _ = app.emit("lcu:checklist-history", &history);
+    Ok(summary)
+}
+
+/// Play sessions, newest first (gap from the settings unless given).
+#[tauri::command]
+pub fn get_play_sessions(
+    gap_minutes: Option<u32>,
+) -> Result<Vec<crate::checklist::session::PlaySession>, String> {
+    let gap = gap_minutes.unwrap_or_else(|| load_companion_config().sessions.gap_minutes);
+    Ok(crate::checklist::session::sessions(&load_all()?, gap))
+}
+
+/// Focus points picked in the current champ select (also sent as `checklist:focus`).
+#[tauri::command]
+pub fn get_focus(
+    state: State<'_, Arc<AppState>>,
+) -> Vec<crate::checklist::focus::FocusSuggestion> {
+    state.focus.lock().map(|f| f.clone()).unwrap_or_default()
+}
+
+/// Goals with their progress and per-game history, oldest first.
+#[tauri::command]
+pub fn get_goals() -> Result<Vec<crate::goals::Goal>, String> {
+    crate::goals::store::load_all()
+}
+
+/// Create or edit a goal; changing its condition, mode, target or window restarts it.
+#[tauri::command]
+pub fn save_goal(goal: crate::goals::Goal) -> Result<Vec<crate::goals::Goal>, String> {
+    crate::goals::save_goal(goal)
+}
+
+#[tauri::command]
+pub fn delete_goal(id: String) -> Result<Vec<crate::goals::Goal>, String> {
+    crate::goals::store::delete(&id)
+}
+
+/// Database schema version, history size and any problem from the JSON import.
+#[tauri::command]
+pub fn get_storage_status() -> Result<crate::db::StorageStatus, String> {
+    crate::db::status()
+}
+
 /// Runs app connectivity test and sets `base_stable_internet` from the result.
 fn apply_internet_check(data: &mut ProgressionSave) {
     progression::apply_internet_result(data, progression::check_internet());
 }
 
 #[tauri::command]
-pub fn load_progression() -> ProgressionSave {
+pub fn load_progression() -> Result<ProgressionSave, String> {
     progression::store::load()
 }
 
 #[tauri::command]
-pub fn get_progression() -> ProgressionSave {
-    let mut data = progression::store::load();
+pub fn get_progression() -> Result<ProgressionSave, String> {
+    let mut data = progression::store::load()?;
     apply_internet_check(&mut data);
     data.updated_at_ms = crate::checklist::evaluator::now_millis();
-    progression::store::save(data.clone())
+    progression::store::save(data)
 }
 
 #[tauri::command]
-pub fn test_internet_for_progression() -> ProgressionSave {
-    let mut data = progression::store::load();
+pub fn test_internet_for_progression() -> Result<ProgressionSave, String> {
+    let mut data = progression::store::load()?;
     apply_internet_check(&mut data);
     data.updated_at_ms = crate::checklist::evaluator::now_millis();
-    progression::store::save(data.clone())
+    progression::store::save(data)
 }
 
 #[tauri::command]
-pub fn confirm_internet_for_progression(online: bool) -> ProgressionSave {
-    let mut data = progression::store::load();
+pub fn confirm_internet_for_progression(online: bool) -> Result<ProgressionSave, String> {
+    let mut data = progression::store::load()?;
     progression::apply_internet_result(&mut data, online);
     data.updated_at_ms = crate::checklist::evaluator::now_millis();
-    progression::store::save(data.clone())
+    progression::store::save(data)
 }
 
 #[tauri::command]
 pub fn save_progression(mut data: ProgressionSave) -> Result<ProgressionSave, String> {
     data.updated_at_ms = crate::checklist::evaluator::now_millis();
-    Ok(progression::store::save(data))
+    progression::store::save(data)
 }
 
 #[tauri::command]
@@ -0,0 +1,131 @@
+//! One-time import of the pre-database JSON files (`checklists.json`, `progression.json`,
+//! `lp_history.json`).
+
+use super::{get_meta, set_meta, sql_err};
+use crate::checklist::evaluator::{now_millis, SavedChecklist};
+use crate::progression::ProgressionSave;
+use crate::rank::LpHistoryEntry;
+use rusqlite::Connection;
+use serde::de::DeserializeOwned;
+use std::path::Path;
+
+const IMPORTED_KEY: &str = "legacy_json_imported_at";
+const LP_IMPORTED_KEY: &str = "legacy_lp_history_imported_at";
+pub const IMPORT_ERROR_KEY: &str = "legacy_json_import_error";
+
+/// `None` when the file does not exist; unreadable files are reported in `problems`.
+fn read_legacy<T: DeserializeOwned>(path: &Path, problems: &mut Vec<String>) -> Option<T> {
+    let raw = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&raw) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            problems.push(format!(
+                "{name} could not be imported and was left in place: {e}"
+            ));
+            None
+        }
+    }
+}
+
+/// Copy the legacy files into the database in one transaction, then rename them `*.imported`.
+pub fn import_legacy_json(conn: &mut Connection, dir: &Path) -> Result<(), String> {
+    if get_meta(conn, IMPORTED_KEY)?.is_some() {
+        return Ok(());
+    }
+    let checklists_path = dir.join("checklists.json");
+    let progression_path = dir.join("progression.json");
+    let mut problems = Vec::new();
+    let checklists: Option<Vec<SavedChecklist>> = read_legacy(&checklists_path, &mut problems);
+    let progression: Option<ProgressionSave> = read_legacy(&progression_path, &mut problems);
+
+    let tx = conn.transaction().map_err(sql_err)?;
+    for entry in checklists.iter().flatten() {
+        crate::checklist::store::insert_if_missing(&tx, entry)?;
+    }
+    if let Some(data) = &progression {
+        crate::progression::store::save_in(&tx, data)?;
+    }
+    set_meta(&tx, IMPORTED_KEY, &now_millis().to_string())?;
+    if !problems.is_empty() {
+        set_meta(&tx, IMPORT_ERROR_KEY, &problems.join("; "))?;
+    }
+    tx.commit().map_err(sql_err)?;
+
+    for (path, imported) in [
+        (checklists_path, checklists.is_some()),
+        (progression_path, progression.is_some()),
+    ] {
+        if imported {
+            let _ = std::fs::rename(&path, path.with_extension("json.imported"));
+        }
+    }
+    Ok(())
+}
+
+/// Copy `lp_history.json` into the database once, then rename it `lp_history.json.imported`.
+pub fn import_legacy_lp_history(conn: &mut Connection, dir: &Path) -> Result<(), String> {
+    if get_meta(conn, LP_IMPORTED_KEY)?.is_some() {
+        return Ok(());
+    }
+    let path = dir.join("lp_history.json");
+    let mut problems = Vec::new();
+    let history: Option<Vec<LpHistoryEntry>> = read_legacy(&path, &mut problems);
+
+    let tx = conn.transaction().map_err(sql_err)?;
+    for entry in history.iter().flatten() {
+        crate::rank::store::insert_in(&tx, entry)?;
+    }
+    set_meta(&tx, LP_IMPORTED_KEY, &now_millis().to_string())?;
+    if !problems.is_empty() {
+        if let Some(earlier) = get_meta(&tx, IMPORT_ERROR_KEY)? {
+            problems.insert(0, earlier);
+        }
+        set_meta(&tx, IMPORT_ERROR_KEY, &problems.join("; "))?;
+    }
+    tx.commit().map_err(sql_err)?;
+
+    if history.is_some() {
+        let _ = std::fs::rename(&path, path.with_extension("json.imported"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+    use crate::db;
+
+    #[test]
+    fn imports_once_and_keeps_corrupt_files() {
+        let dir = std::env::temp_dir().join(format!("companion-import-{}", now_millis()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let entry = test_checklist(Default::default());
+        let json = serde_json::to_string(&vec![entry.clone()]).unwrap();
+        std::fs::write(dir.join("checklists.json"), json).unwrap();
+        std::fs::write(dir.join("progression.json"), "{ not json").unwrap();
+        let lp = r#"[{"takenAtMs": 5, "queueType": "RANKED_SOLO_5x5", "tier": "GOLD",
+            "division": "II", "leaguePoints": 40, "source": "connect", "change": "initial"}]"#;
+        std::fs::write(dir.join("lp_history.json"), lp).unwrap();
+
+        let mut conn = db::test_conn();
+        for _ in 0..2 {
+            import_legacy_json(&mut conn, &dir).unwrap();
+            import_legacy_lp_history(&mut conn, &dir).unwrap();
+        }
+
+        let stored = crate::checklist::store::load_all_in(&conn).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].id, entry.id);
+        assert!(dir.join("checklists.json.imported").exists());
+        assert!(dir.join("progression.json").exists());
+        let error = get_meta(&conn, IMPORT_ERROR_KEY).unwrap().unwrap();
+        assert!(error.contains("progression.json"));
+        let lp_history = crate::rank::store::load_in(&conn).unwrap();
+        assert_eq!(lp_history.len(), 1);
+        assert_eq!(lp_history[0].rank.league_points, 40);
+        assert!(dir.join("lp_history.json.imported").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
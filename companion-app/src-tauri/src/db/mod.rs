@@ -0,0 +1,230 @@
+//! Embedded SQLite database (`companion.db`) with versioned migrations.
+
+pub mod import;
+
+use crate::checklist::SavedChecklist;
+use crate::rank::LpHistoryEntry;
+use rusqlite::{Connection, OptionalExtension};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Applied in order; `PRAGMA user_version` is the number of migrations already run.
+const MIGRATIONS: &[&str] = &[
+    // 1: checklists, progression and key/value metadata.
+    "CREATE TABLE meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    CREATE TABLE checklists (
+        id TEXT PRIMARY KEY,
+        saved_at_ms INTEGER NOT NULL,
+        game_id INTEGER,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX checklists_saved_at ON checklists (saved_at_ms DESC);
+    CREATE INDEX checklists_game_id ON checklists (game_id);
+    CREATE TABLE progression (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        data TEXT NOT NULL
+    );",
+    // 2: indexed columns for history queries, filled from the stored JSON.
+    "ALTER TABLE checklists ADD COLUMN champion_id INTEGER;
+    ALTER TABLE checklists ADD COLUMN queue_id INTEGER;
+    ALTER TABLE checklists ADD COLUMN win INTEGER;
+    ALTER TABLE checklists ADD COLUMN ranked INTEGER;
+    ALTER TABLE checklists ADD COLUMN score REAL;
+    UPDATE checklists SET
+        champion_id = json_extract(data, '$.stats.championId'),
+        queue_id = json_extract(data, '$.stats.queueId'),
+        win = json_extract(data, '$.stats.win'),
+        ranked = json_extract(data, '$.stats.ranked'),
+        score = json_extract(data, '$.score');
+    CREATE INDEX checklists_champion ON checklists (champion_id);
+    CREATE INDEX checklists_queue ON checklists (queue_id);
+    CREATE INDEX checklists_score ON checklists (score);",
+    // 3: player goals with their per-game history.
+    "CREATE TABLE goals (
+        id TEXT PRIMARY KEY,
+        created_at_ms INTEGER NOT NULL,
+        data TEXT NOT NULL
+    );",
+    // 4: live checklist draft and CS samples of the game in progress, for crash recovery.
+    "CREATE TABLE live_draft (
+        id INTEGER PRIMARY KEY CHECK (id = 1),
+        data TEXT NOT NULL
+    );",
+    // 5: LP history log, oldest first (filled from `lp_history.json` by `init`).
+    "CREATE TABLE lp_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        taken_at_ms INTEGER NOT NULL,
+        queue_type TEXT NOT NULL,
+        data TEXT NOT NULL
+    );
+    CREATE INDEX lp_history_taken_at ON lp_history (taken_at_ms);",
+];
+
+/// Per-user directory holding the config, the database and the game archive.
+pub(crate) fn data_dir() -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".into());
+        PathBuf::from(appdata).join("Lelanation").join("Companion")
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
+        PathBuf::from(home)
+            .join(".config")
+            .join("lelanation-companion")
+    }
+}
+
+pub(crate) fn sql_err(e: rusqlite::Error) -> String {
+    format!("Database error: {e}")
+}
+
+/// Run pending migrations, each in its own transaction.
+pub fn migrate(conn: &mut Connection) -> Result<u32, String> {
+    let current: u32 = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(sql_err)?;
+    if current as usize > MIGRATIONS.len() {
+        return Err(format!(
+            "Database schema {current} is newer than this version of the app"
+        ));
+    }
+    for (index, sql) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let tx = conn.transaction().map_err(sql_err)?;
+        tx.execute_batch(sql)
+            .map_err(|e| format!("Migration {} failed: {e}", index + 1))?;
+        tx.pragma_update(None, "user_version", index as u32 + 1)
+            .map_err(sql_err)?;
+        tx.commit().map_err(sql_err)?;
+    }
+    Ok(MIGRATIONS.len() as u32)
+}
+
+/// Outcome of `init`; once it failed, `connect` returns that error rather than use a database
+/// at an unknown schema.
+static INIT: OnceLock<Result<(), String>> = OnceLock::new();
+
+fn open_file() -> Result<Connection, String> {
+    std::fs::create_dir_all(data_dir()).map_err(|e| e.to_string())?;
+    let conn = Connection::open(data_dir().join("companion.db")).map_err(sql_err)?;
+    conn.busy_timeout(Duration::from_secs(5)).map_err(sql_err)?;
+    conn.pragma_update(None, "journal_mode", "WAL")
+        .map_err(sql_err)?;
+    Ok(conn)
+}
+
+/// Migrate the database and import the legacy JSON files the first time; runs once, in the
+/// app setup.
+pub fn init() -> Result<(), String> {
+    INIT.get_or_init(|| {
+        let mut conn = open_file()?;
+        migrate(&mut conn)?;
+        import::import_legacy_json(&mut conn, &data_dir())?;
+        import::import_legacy_lp_history(&mut conn, &data_dir())
+    })
+    .clone()
+}
+
+/// Open the database `init` prepared.
+pub fn connect() -> Result<Connection, String> {
+    if let Some(Err(e)) = INIT.get() {
+        return Err(format!("Database unavailable: {e}"));
+    }
+    open_file()
+}
+
+/// Migrated in-memory database for tests.
+#[cfg(test)]
+pub(crate) fn test_conn() -> Connection {
+    let mut conn = Connection::open_in_memory().unwrap();
+    migrate(&mut conn).unwrap();
+    conn
+}
+
+pub fn get_meta(conn: &Connection, key: &str) -> Result<Option<String>, String> {
+    conn.query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| {
+        row.get(0)
+    })
+    .optional()
+    .map_err(sql_err)
+}
+
+pub fn set_meta(conn: &Connection, key: &str, value: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO meta (key, value) VALUES (?1, ?2)
+         ON CONFLICT (key) DO UPDATE SET value = excluded.value",
+        [key, value],
+    )
+    .map(|_| ())
+    .map_err(sql_err)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageStatus {
+    pub schema_version: u32,
+    pub checklist_count: u32,
+    /// Checklists and LP history entries this version cannot read; they are kept as is.
+    pub undecodable_count: u32,
+    /// Problem met while importing the old JSON files, if any.
+    pub import_error: Option<String>,
+}
+
+/// Rows of `table` whose `data` column does not decode as `T`.
+fn count_undecodable<T: DeserializeOwned>(conn: &Connection, table: &str) -> Result<u32, String> {
+    let mut stmt = conn
+        .prepare(&format!("SELECT data FROM {table}"))
+        .map_err(sql_err)?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(sql_err)?;
+    let mut count = 0;
+    for raw in rows {
+        if serde_json::from_str::<T>(&raw.map_err(sql_err)?).is_err() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+pub fn status() -> Result<StorageStatus, String> {
+    let conn = connect()?;
+    let schema_version = conn
+        .pragma_query_value(None, "user_version", |row| row.get(0))
+        .map_err(sql_err)?;
+    let checklist_count = conn
+        .query_row("SELECT COUNT(*) FROM checklists", [], |row| row.get(0))
+        .map_err(sql_err)?;
+    Ok(StorageStatus {
+        schema_version,
+        checklist_count,
+        undecodable_count: count_undecodable::<SavedChecklist>(&conn, "checklists")?
+            + count_undecodable::<LpHistoryEntry>(&conn, "lp_history")?,
+        import_error: get_meta(&conn, import::IMPORT_ERROR_KEY)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrations_run_once() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        assert_eq!(migrate(&mut conn).unwrap(), MIGRATIONS.len() as u32);
+        assert_eq!(migrate(&mut conn).unwrap(), MIGRATIONS.len() as u32);
+        set_meta(&conn, "k", "v").unwrap();
+        set_meta(&conn, "k", "w").unwrap();
+        assert_eq!(get_meta(&conn, "k").unwrap().as_deref(), Some("w"));
+
+        conn.pragma_update(None, "user_version", 99).unwrap();
+        assert!(migrate(&mut conn).is_err());
+    }
+}
@@ -0,0 +1,335 @@
+//! Player goals over upcoming games: "CS@10 ≥ 80 in 7 of my next 10 games",
+//! "≤ 3 deaths for 5 games in a row".
+
+pub mod store;
+
+use crate::checklist::evaluator::{effective_kind, now_millis, CheckResultKind};
+use crate::checklist::expr::{self, Variables};
+use crate::checklist::SavedChecklist;
+use crate::db::{self, sql_err};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum GoalMode {
+    /// `target` passing games out of the next `window`.
+    #[default]
+    Window,
+    /// `target` passing games in a row.
+    Streak,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum GoalStatus {
+    #[default]
+    Active,
+    Completed,
+    /// Window goals only: too few games left to reach the target.
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalGame {
+    pub checklist_id: String,
+    pub game_id: Option<u64>,
+    pub saved_at_ms: i64,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Goal {
+    #[serde(default)]
+    pub id: String,
+    pub label: String,
+    /// Checklist item that must be checked (`cs_at_10`, `custom:kda`, …)…
+    #[serde(default)]
+    pub item_id: Option<String>,
+    /// …or an expression over the game's stats (`cs_at_10 >= 80`, `deaths <= 3`).
+    #[serde(default)]
+    pub expression: Option<String>,
+    #[serde(default)]
+    pub mode: GoalMode,
+    pub target: u32,
+    /// Games in the window (window mode).
+    #[serde(default)]
+    pub window: u32,
+    #[serde(default)]
+    pub created_at_ms: i64,
+    #[serde(default)]
+    pub status: GoalStatus,
+    #[serde(default)]
+    pub completed_at_ms: Option<i64>,
+    #[serde(default)]
+    pub best_streak: u32,
+    /// Counted games, oldest first; games where the goal cannot be measured are left out.
+    #[serde(default)]
+    pub history: Vec<GoalGame>,
+}
+
+/// Payload of `goal:progress` and `goal:completed`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoalProgress {
+    pub goal_id: String,
+    pub label: String,
+    pub mode: GoalMode,
+    pub status: GoalStatus,
+    /// Whether the game just saved passed.
+    pub passed: bool,
+    /// Passing games so far (window) or the current streak.
+    pub progress: u32,
+    pub target: u32,
+    pub games: u32,
+    pub window: u32,
+}
+
+impl Goal {
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        if self.label.trim().is_empty() {
+            errors.push("label must not be empty".to_string());
+        }
+        match (&self.item_id, &self.expression) {
+            (Some(_), None) => {}
+            (None, Some(src)) => match expr::parse(src) {
+                Ok(parsed) => {
+                    let mut names = Vec::new();
+                    expr::variables(&parsed, &mut names);
+                    for name in names.iter().filter(|n| !Variables::is_known(n)) {
+                        errors.push(format!("unknown variable `{name}`"));
+                    }
+                }
+                Err(e) => errors.push(e),
+            },
+            _ => errors.push("set either itemId or expression".to_string()),
+        }
+        if self.target == 0 {
+            errors.push("target must be at least 1".to_string());
+        }
+        if self.mode == GoalMode::Window && self.window < self.target {
+            errors.push("window must not be smaller than target".to_string());
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+
+    /// Whether `entry` meets the goal; `None` when it cannot be measured for that game.
+    fn passes(&self, entry: &SavedChecklist) -> Option<bool> {
+        if let Some(id) = &self.item_id {
+            let item = entry.items.iter().find(|i| &i.id == id)?;
+            return match effective_kind(item, entry.unscored) {
+                CheckResultKind::Unmeasurable => None,
+                kind => Some(kind == CheckResultKind::Checked),
+            };
+        }
+        let parsed = expr::parse(self.expression.as_deref()?).ok()?;
+        expr::eval(&parsed, &Variables::new(&entry.stats))
+            .ok()
+            .map(|v| v != 0.0)
+    }
+
+    fn current_streak(&self) -> u32 {
+        self.history.iter().rev().take_while(|g| g.passed).count() as u32
+    }
+
+    fn passed_count(&self) -> u32 {
+        self.history.iter().filter(|g| g.passed).count() as u32
+    }
+
+    fn progress(&self, passed: bool) -> GoalProgress {
+        GoalProgress {
+            goal_id: self.id.clone(),
+            label: self.label.clone(),
+            mode: self.mode,
+            status: self.status,
+            passed,
+            progress: match self.mode {
+                GoalMode::Window => self.passed_count(),
+                GoalMode::Streak => self.current_streak(),
+            },
+            target: self.target,
+            games: self.history.len() as u32,
+            window: self.window,
+        }
+    }
+
+    /// Count a saved game toward an active goal; `None` when it does not count.
+    pub fn record(&mut self, entry: &SavedChecklist) -> Option<GoalProgress> {
+        if self.status != GoalStatus::Active
+            || entry.unscored
+            || entry.saved_at_ms < self.created_at_ms
+            || self.history.iter().any(|g| g.checklist_id == entry.id)
+        {
+            return None;
+        }
+        let passed = self.passes(entry)?;
+        self.history.push(GoalGame {
+            checklist_id: entry.id.clone(),
+            game_id: entry.stats.game_id,
+            saved_at_ms: entry.saved_at_ms,
+            passed,
+        });
+        self.best_streak = self.best_streak.max(self.current_streak());
+        let games = self.history.len() as u32;
+        let done = match self.mode {
+            GoalMode::Window => self.passed_count() >= self.target,
+            GoalMode::Streak => self.current_streak() >= self.target,
+        };
+        if done {
+            self.status = GoalStatus::Completed;
+            self.completed_at_ms = Some(entry.saved_at_ms);
+        } else if self.mode == GoalMode::Window
+            && self.passed_count() + self.window.saturating_sub(games) < self.target
+        {
+            self.status = GoalStatus::Failed;
+        }
+        Some(self.progress(passed))
+    }
+}
+
+/// Create or edit a goal. Changing what it measures restarts its history.
+pub fn save_goal(mut goal: Goal) -> Result<Vec<Goal>, String> {
+    goal.validate()?;
+    let conn = db::connect()?;
+    let existing = store::load_all_in(&conn)?
+        .into_iter()
+        .find(|g| !goal.id.is_empty() && g.id == goal.id);
+    match existing {
+        Some(existing)
+            if existing.item_id == goal.item_id
+                && existing.expression == goal.expression
+                && existing.mode == goal.mode
+                && existing.target == goal.target
+                && existing.window == goal.window =>
+        {
+            goal.created_at_ms = existing.created_at_ms;
+            goal.status = existing.status;
+            goal.completed_at_ms = existing.completed_at_ms;
+            goal.best_streak = existing.best_streak;
+            goal.history = existing.history;
+        }
+        _ => {
+            if goal.id.is_empty() {
+                goal.id = format!("goal-{}", now_millis());
+            }
+            goal.created_at_ms = now_millis();
+            goal.status = GoalStatus::Active;
+            goal.completed_at_ms = None;
+            goal.best_streak = 0;
+            goal.history.clear();
+        }
+    }
+    store::upsert(&conn, &goal)?;
+    store::load_all_in(&conn)
+}
+
+pub(crate) fn record_game_in(
+    conn: &mut Connection,
+    entry: &SavedChecklist,
+) -> Result<Vec<GoalProgress>, String> {
+    let tx = conn.transaction().map_err(sql_err)?;
+    let mut updates = Vec::new();
+    for mut goal in store::load_all_in(&tx)? {
+        if let Some(progress) = goal.record(entry) {
+            store::upsert(&tx, &goal)?;
+            updates.push(progress);
+        }
+    }
+    tx.commit().map_err(sql_err)?;
+    Ok(updates)
+}
+
+/// Count a newly saved checklist toward every active goal.
+pub fn record_game(entry: &SavedChecklist) -> Result<Vec<GoalProgress>, String> {
+    record_game_in(&mut db::connect()?, entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checklist::test_checklist;
+    use crate::postgame::PostGameStats;
+
+    fn game(id: &str, deaths: u32) -> SavedChecklist {
+        let stats = PostGameStats {
+            game_duration_seconds: 1800,
+            deaths,
+            ..Default::default()
+        };
+        let mut entry = test_checklist(stats);
+        entry.id = id.into();
+        entry.saved_at_ms = 10;
+        entry
+    }
+
+    #[test]
+    fn tracks_windows_and_streaks() {
+        let mut conn = db::test_conn();
+        let streak = Goal {
+            id: "few-deaths".into(),
+            label: "≤ 3 deaths, 2 in a row".into(),
+            item_id: None,
+            expression: Some("deaths <= 3".into()),
+            mode: GoalMode::Streak,
+            target: 2,
+            window: 0,
+            created_at_ms: 0,
+            status: GoalStatus::Active,
+            completed_at_ms: None,
+            best_streak: 0,
+            history: Vec::new(),
+        };
+        let window = Goal {
+            id: "survive".into(),
+            item_id: Some("survival".into()),
+            expression: None,
+            mode: GoalMode::Window,
+            target: 3,
+            window: 3,
+            ..streak.clone()
+        };
+        assert!(streak.validate().is_ok() && window.validate().is_ok());
+        assert!(Goal {
+            window: 1,
+            ..window.clone()
+        }
+        .validate()
+        .is_err());
+        store::upsert(&conn, &streak).unwrap();
+        store::upsert(&conn, &window).unwrap();
+
+        let first = record_game_in(&mut conn, &game("g1", 2)).unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(record_game_in(&mut conn, &game("g1", 2))
+            .unwrap()
+            .is_empty());
+        let second = record_game_in(&mut conn, &game("g2", 9)).unwrap();
+        let window_update = second.iter().find(|p| p.goal_id == "survive").unwrap();
+        assert_eq!(window_update.status, GoalStatus::Failed);
+        assert_eq!(record_game_in(&mut conn, &game("g3", 1)).unwrap().len(), 1);
+
+        let last = record_game_in(&mut conn, &game("g4", 0)).unwrap();
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].status, GoalStatus::Completed);
+
+        let goals = store::load_all_in(&conn).unwrap();
+        let streak = goals.iter().find(|g| g.id == "few-deaths").unwrap();
+        assert_eq!(streak.history.len(), 4);
+        assert_eq!(streak.best_streak, 2);
+
+        // A restarted goal moves to its new place in the list.
+        let restarted = Goal {
+            created_at_ms: 100,
+            ..streak.clone()
+        };
+        store::upsert(&conn, &restarted).unwrap();
+        assert_eq!(store::load_all_in(&conn).unwrap()[1].id, "few-deaths");
+    }
+}
@@ -0,0 +1,45 @@
+//! Goals in the SQLite database, oldest first.
+
+use super::Goal;
+use crate::db::{self, sql_err};
+use rusqlite::{params, Connection};
+
+pub(crate) fn upsert(conn: &Connection, goal: &Goal) -> Result<(), String> {
+    let data = serde_json::to_string(goal).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO goals (id, created_at_ms, data) VALUES (?1, ?2, ?3)
+         ON CONFLICT (id) DO UPDATE SET
+            created_at_ms = excluded.created_at_ms,
+            data = excluded.data",
+        params![goal.id, goal.created_at_ms, data],
+    )
+    .map(|_| ())
+    .map_err(sql_err)
+}
+
+pub(crate) fn load_all_in(conn: &Connection) -> Result<Vec<Goal>, String> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM goals ORDER BY created_at_ms, id")
+        .map_err(sql_err)?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(sql_err)?;
+    let mut goals = Vec::new();
+    for raw in rows {
+        if let Ok(goal) = serde_json::from_str(&raw.map_err(sql_err)?) {
+            goals.push(goal);
+        }
+    }
+    Ok(goals)
+}
+
+pub fn load_all() -> Result<Vec<Goal>, String> {
+    load_all_in(&db::connect()?)
+}
+
+pub fn delete(id: &str) -> Result<Vec<Goal>, String> {
+    let conn = db::connect()?;
+    conn.execute("DELETE FROM goals WHERE id = ?1", [id])
+        .map_err(sql_err)?;
+    load_all_in(&conn)
+}
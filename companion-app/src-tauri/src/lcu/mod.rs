@@ -521,20 +521,53 @@ pub fn resolve_champion_numeric_id(
     0
 }
 
-/// Local player's champion id during champ select, if detectable.
-pub fn fetch_local_champion_id(client: &LcuClient) -> Option<u32> {
+/// Game client version (e.g. `14.23.636.1234`), used to pick patch-specific rules.
+pub fn fetch_game_version(client: &LcuClient) -> Option<String> {
+    let raw = client.get("/lol-patch/v1/game-version").ok()?;
+    serde_json::from_str::<String>(&raw).ok()
+}
+
+/// Id of the game in progress (`gameData.gameId` of the gameflow session).
+pub fn fetch_game_id(client: &LcuClient) -> Option<u64> {
+    let raw = client.get("/lol-gameflow/v1/session").ok()?;
+    let session: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    session
+        .get("gameData")?
+        .get("gameId")?
+        .as_u64()
+        .filter(|&id| id > 0)
+}
+
+/// The local player's `myTeam` entry in the champ select session.
+fn local_champ_select_member(client: &LcuClient) -> Option<serde_json::Value> {
     let raw = client.get("/lol-champ-select/v1/session").ok()?;
     let session: serde_json::Value = serde_json::from_str(&raw).ok()?;
     let local_cell = session.get("localPlayerCellId")?.as_i64()?;
     let my_team = session.get("myTeam")?.as_array()?;
     for member in my_team {
         if member.get("cellId")?.as_i64()? == local_cell {
-            return member.get("championId")?.as_u64().map(|id| id as u32);
+            return Some(member.clone());
         }
     }
     None
 }
 
+/// Local player's champion id during champ select, if detectable.
+pub fn fetch_local_champion_id(client: &LcuClient) -> Option<u32> {
+    local_champ_select_member(client)?
+        .get("championId")?
+        .as_u64()
+        .map(|id| id as u32)
+}
+
+/// `assignedPosition` in draft modes (`TOP`, `JUNGLE`, …); `None` in blind pick.
+pub fn fetch_local_assigned_position(client: &LcuClient) -> Option<String> {
+    let member = local_champ_select_member(client)?;
+    let position =
+        crate::postgame::role::normalize_position(member.get("assignedPosition")?.as_str()?);
+    (!position.is_empty()).then_some(position)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
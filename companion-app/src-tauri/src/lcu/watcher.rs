@@ -1,9 +1,17 @@
 //! Poll LCU gameflow phase and emit Tauri events.
 
-use super::{fetch_gameflow_phase, fetch_local_champion_id, try_auto_apply, LcuClient};
-use crate::checklist::{merge_user_edits, save_entry, to_saved_checklist};
-use crate::live_client::{self, LiveCsSnapshot};
+use super::{
+    fetch_game_id, fetch_game_version, fetch_gameflow_phase, fetch_local_assigned_position,
+    fetch_local_champion_id, try_auto_apply, LcuClient,
+};
+use crate::app_config::load_companion_config;
+use crate::checklist::{
+    checklist_for_game, merge_user_edits, recovery, rules::load_rules, save_entry, session,
+    to_saved_checklist, SavedChecklist,
+};
+use crate::live_client::{self, LiveCsSnapshot, LiveTimeline, TimelineEvent};
 use crate::postgame;
+use crate::rank;
 use crate::state::AppState;
 use std::sync::Arc;
 use std::thread;
@@ -12,6 +20,7 @@ use tauri::{AppHandle, Emitter};
 
 const POLL_INTERVAL: Duration = Duration::from_secs(2);
 const POSTGAME_INITIAL_DELAY: Duration = Duration::from_secs(4);
+const LIVE_PLAYERS_INTERVAL: Duration = Duration::from_secs(10);
 
 pub fn start(app: AppHandle, state: Arc<AppState>) {
     thread::spawn(move || watcher_loop(app, state));
@@ -21,15 +30,24 @@ fn reset_live_snapshot(state: &AppState) {
     if let Ok(mut snap) = state.live_cs_snapshot.lock() {
         *snap = LiveCsSnapshot::default();
     }
+    if let Ok(mut timeline) = state.live_timeline.lock() {
+        *timeline = LiveTimeline::default();
+    }
+}
+
+fn live_timeline_events(state: &AppState) -> Vec<TimelineEvent> {
+    state
+        .live_timeline
+        .lock()
+        .map(|t| t.events.clone())
+        .unwrap_or_default()
 }
 
 fn merge_live_cs(state: &AppState, stats: &mut postgame::PostGameStats) {
+    let minutes = load_companion_config().cs_milestone_minutes();
     if let Ok(snap) = state.live_cs_snapshot.lock() {
-        if stats.cs_at_5.is_none() {
-            stats.cs_at_5 = snap.cs_at_5;
-        }
-        if stats.cs_at_10.is_none() {
-            stats.cs_at_10 = snap.cs_at_10;
+        for (minute, cs) in snap.milestones(&minutes) {
+            stats.cs_milestones.entry(minute).or_insert(cs);
         }
     }
 }
@@ -39,17 +57,88 @@ fn refresh_live_draft(app: &AppHandle, state: &AppState) {
     let Some(snap) = snap else {
         return;
     };
-    let Some(stats) = live_client::fetch_live_stats(&snap) else {
+    let minutes = load_companion_config().cs_milestone_minutes();
+    let Some(mut stats) = live_client::fetch_live_stats(&snap, &minutes) else {
         return;
     };
+    if let Ok(p) = state.assigned_position.lock() {
+        stats.position = p.clone().unwrap_or_default();
+    }
     let mut draft = to_saved_checklist(stats, false);
     draft.auto_saved = false;
+    draft.timeline = live_timeline_events(state);
+    draft.focus = focus_ids(state);
     if let Ok(mut d) = state.checklist_draft.lock() {
+        if let Some(prev) = d.as_ref() {
+            draft.id = prev.id.clone();
+            merge_user_edits(&mut draft, prev);
+        }
         *d = Some(draft.clone());
     }
     let _ = app.emit("lcu:checklist-draft", &draft);
 }
 
+/// Failures of the crash-recovery save (`live:recovery-error`).
+fn emit_recovery_error(app: &AppHandle, result: Result<(), String>) {
+    if let Err(e) = result {
+        let _ = app.emit("live:recovery-error", serde_json::json!({ "message": e }));
+    }
+}
+
+/// Save the draft and CS samples so a restart during the game can pick them up.
+pub(crate) fn persist_live_state(app: &AppHandle, state: &AppState) {
+    let game_id = state.live_game_id.lock().ok().and_then(|g| *g);
+    let draft = state.checklist_draft.lock().ok().and_then(|d| d.clone());
+    let samples = state
+        .live_cs_snapshot
+        .lock()
+        .map(|s| s.samples.clone())
+        .unwrap_or_default();
+    emit_recovery_error(app, recovery::save(game_id, draft, samples));
+}
+
+/// After a restart, pick up the draft and CS samples saved for the game still running.
+fn restore_live_state(app: &AppHandle, state: &AppState, client: &LcuClient) {
+    let phase = fetch_gameflow_phase(client).unwrap_or_default();
+    let game_id = fetch_game_id(client);
+    let saved = match recovery::take_resumable(game_id, &phase) {
+        Ok(Some(saved)) => saved,
+        Ok(None) => return,
+        Err(e) => {
+            emit_recovery_error(app, Err(e));
+            return;
+        }
+    };
+    if let Ok(mut g) = state.live_game_id.lock() {
+        *g = game_id;
+    }
+    if let Ok(mut snap) = state.live_cs_snapshot.lock() {
+        snap.samples = saved.cs_samples;
+    }
+    if let Some(draft) = &saved.draft {
+        let _ = app.emit("lcu:checklist-draft", draft);
+    }
+    if let Ok(mut d) = state.checklist_draft.lock() {
+        *d = saved.draft;
+    }
+}
+
+/// Append changed ranked queues to the LP history and emit the new entries.
+fn record_rank_snapshot(app: &AppHandle, client: &LcuClient, source: &str, game_id: Option<u64>) {
+    let Some(summoner_id) = postgame::local_summoner_id(client) else {
+        return;
+    };
+    match rank::record_snapshot(client, summoner_id, source, game_id) {
+        Ok(added) if !added.is_empty() => {
+            let _ = app.emit("lcu:lp-history", &added);
+        }
+        Ok(_) => {}
+        Err(e) => {
+            let _ = app.emit("lcu:lp-history-error", serde_json::json!({ "message": e }));
+        }
+    }
+}
+
 fn handle_postgame(app: &AppHandle, state: &Arc<AppState>) {
     let app = app.clone();
     let state = Arc::clone(state);
@@ -62,24 +151,73 @@ fn handle_postgame(app: &AppHandle, state: &Arc<AppState>) {
             return;
         }
         match postgame::fetch_postgame_stats(&client) {
-            Ok(mut stats) => {
+            Ok((mut stats, archive_error)) => {
+                if let Some(e) = archive_error {
+                    let _ = app.emit("lcu:archive-error", serde_json::json!({ "message": e }));
+                }
                 merge_live_cs(&state, &mut stats);
+                if stats.position.is_empty() {
+                    if let Ok(p) = state.assigned_position.lock() {
+                        stats.position = p.clone().unwrap_or_default();
+                    }
+                }
+                let config = load_companion_config();
+                if let Some(summoner_id) = postgame::local_summoner_id(&client) {
+                    let _ = postgame::game_timeline::enrich_from_timeline(
+                        &client,
+                        summoner_id,
+                        &mut stats,
+                        &config.cs_milestone_minutes(),
+                    );
+                }
                 let _ = app.emit("lcu:postgame-stats", &stats);
 
-                let mut saved = to_saved_checklist(stats, true);
-                if let Ok(draft) = state.checklist_draft.lock() {
-                    if let Some(prev) = draft.as_ref() {
-                        merge_user_edits(&mut saved, prev);
+                let game_id = stats.game_id;
+                let game_class = postgame::game_kind::classify(&stats);
+                match checklist_for_game(stats, true, &config.game_scoring, &load_rules()) {
+                    Some(mut saved) => {
+                        saved.timeline = live_timeline_events(&state);
+                        saved.focus = focus_ids(&state);
+                        if let Ok(draft) = state.checklist_draft.lock() {
+                            if let Some(prev) = draft.as_ref() {
+                                merge_user_edits(&mut saved, prev);
+                            }
+                        }
+                        if let Ok(mut draft) = state.checklist_draft.lock() {
+                            *draft = Some(saved.clone());
+                        }
+                        match save_entry(saved.clone()) {
+                            Ok(history) => {
+                                let _ = app.emit("lcu:checklist-saved", &saved);
+                                let _ = app.emit("lcu:checklist-history", &history);
+                                emit_goal_progress(&app, &saved);
+                                emit_focus_review(&app, &state, &saved);
+                                let sessions = &config.sessions;
+                                if let Some(warning) = session::detect_tilt(&history, sessions) {
+                                    let _ = app.emit("session:tilt-warning", &warning);
+                                }
+                            }
+                            Err(e) => {
+                                clear_focus(&state);
+                                let _ = app.emit(
+                                    "lcu:checklist-save-error",
+                                    serde_json::json!({ "message": e }),
+                                );
+                            }
+                        }
+                    }
+                    None => {
+                        if let Ok(mut draft) = state.checklist_draft.lock() {
+                            *draft = None;
+                        }
+                        clear_focus(&state);
+                        let _ = app.emit("lcu:checklist-skipped", &game_class);
                     }
                 }
-                if let Ok(mut draft) = state.checklist_draft.lock() {
-                    *draft = Some(saved.clone());
-                }
-                let history = save_entry(saved.clone());
-                let _ = app.emit("lcu:checklist-saved", &saved);
-                let _ = app.emit("lcu:checklist-history", &history);
+                record_rank_snapshot(&app, &client, "postgame", game_id);
             }
             Err(e) => {
+                clear_focus(&state);
                 let _ = app.emit(
                     "lcu:postgame-error",
                     serde_json::json!({ "message": e }),
@@ -87,21 +225,107 @@ fn handle_postgame(app: &AppHandle, state: &Arc<AppState>) {
             }
         }
         reset_live_snapshot(&state);
+        emit_recovery_error(&app, recovery::clear());
     });
 }
 
+fn focus_ids(state: &AppState) -> Vec<String> {
+    state
+        .focus
+        .lock()
+        .map(|f| f.iter().map(|s| s.id.clone()).collect())
+        .unwrap_or_default()
+}
+
+/// Pick focus points from recent games on this champion and role (`checklist:focus`).
+fn emit_focus(app: &AppHandle, state: &AppState) {
+    let champion_id = state.champion_id.lock().ok().and_then(|c| *c);
+    let position = state
+        .assigned_position
+        .lock()
+        .ok()
+        .and_then(|p| p.clone())
+        .unwrap_or_default();
+    let Ok(history) = crate::checklist::focus::recent_games(champion_id, &position) else {
+        return;
+    };
+    let focus = crate::checklist::focus::suggest(&history, champion_id, &position);
+    if let Ok(mut f) = state.focus.lock() {
+        *f = focus.clone();
+    }
+    let _ = app.emit("checklist:focus", &focus);
+}
+
+/// Drop focus points picked for a game that will not be saved.
+fn clear_focus(state: &AppState) {
+    if let Ok(mut f) = state.focus.lock() {
+        f.clear();
+    }
+}
+
+/// Compare the focus items of the saved game with their pass rate (`checklist:focus-review`).
+fn emit_focus_review(app: &AppHandle, state: &AppState, saved: &SavedChecklist) {
+    let focus = state
+        .focus
+        .lock()
+        .map(|mut f| std::mem::take(&mut *f))
+        .unwrap_or_default();
+    if !focus.is_empty() {
+        let review = crate::checklist::focus::review(&focus, saved);
+        let _ = app.emit("checklist:focus-review", &review);
+    }
+}
+
+/// Count the saved game toward the player's goals (`goal:progress`, `goal:completed`,
+/// `goal:error`).
+fn emit_goal_progress(app: &AppHandle, saved: &SavedChecklist) {
+    let updates = match crate::goals::record_game(saved) {
+        Ok(updates) => updates,
+        Err(e) => {
+            let _ = app.emit("goal:error", serde_json::json!({ "message": e }));
+            return;
+        }
+    };
+    for progress in updates {
+        let _ = app.emit("goal:progress", &progress);
+        if progress.status == crate::goals::GoalStatus::Completed {
+            let _ = app.emit("goal:completed", &progress);
+        }
+    }
+}
+
 fn sample_live_cs(state: &AppState) {
     if let Ok(mut snap) = state.live_cs_snapshot.lock() {
-        live_client::sample_cs_milestones(&mut snap);
+        live_client::sample_cs(&mut snap);
+    }
+    if let Ok(mut timeline) = state.live_timeline.lock() {
+        live_client::poll_events(&mut timeline);
     }
 }
 
+fn emit_objective_timers(app: &AppHandle, state: &AppState) {
+    let Some(game_time) = live_client::game_time_seconds() else {
+        return;
+    };
+    let version = state
+        .game_version
+        .lock()
+        .map(|v| v.clone())
+        .unwrap_or_default();
+    let events = live_timeline_events(state);
+    let timers =
+        live_client::objective_timers(&events, live_client::respawn_rules(&version), game_time);
+    let _ = app.emit("live:objective-timers", &timers);
+}
+
 fn watcher_loop(app: AppHandle, state: Arc<AppState>) {
     let mut was_connected = false;
     let mut last_phase = String::new();
     let mut last_draft_emit = std::time::Instant::now()
         .checked_sub(Duration::from_secs(60))
         .unwrap_or_else(std::time::Instant::now);
+    let mut last_players_emit = last_draft_emit;
+    let mut restored = false;
 
     loop {
         thread::sleep(POLL_INTERVAL);
@@ -138,6 +362,11 @@ fn watcher_loop(app: AppHandle, state: Arc<AppState>) {
                 *c = true;
             }
             let _ = app.emit("lcu:connected", ());
+            record_rank_snapshot(&app, &client, "connect", None);
+            if !restored {
+                restored = true;
+                restore_live_state(&app, &state, &client);
+            }
         }
 
         let phase = fetch_gameflow_phase(&client).unwrap_or_else(|_| "None".into());
@@ -149,10 +378,28 @@ fn watcher_loop(app: AppHandle, state: Arc<AppState>) {
             }
             let _ = app.emit("lcu:phase-changed", phase.clone());
 
+            // Dodged or left champ select: the focus points were for a game that never started.
+            if previous == "ChampSelect" && phase != "GameStart" && phase != "InProgress" {
+                clear_focus(&state);
+            }
+
             if phase == "InProgress" {
-                reset_live_snapshot(&state);
-                if let Ok(mut d) = state.checklist_draft.lock() {
-                    *d = None;
+                if let Ok(mut v) = state.game_version.lock() {
+                    *v = fetch_game_version(&client).unwrap_or_default();
+                }
+                // Back from a reconnect or a restart: keep what was collected for this game.
+                let game_id = fetch_game_id(&client);
+                let same_game =
+                    game_id.is_some() && state.live_game_id.lock().is_ok_and(|g| *g == game_id);
+                if !same_game {
+                    reset_live_snapshot(&state);
+                    if let Ok(mut d) = state.checklist_draft.lock() {
+                        *d = None;
+                    }
+                    emit_recovery_error(&app, recovery::clear());
+                }
+                if let Ok(mut g) = state.live_game_id.lock() {
+                    *g = game_id;
                 }
             }
 
@@ -163,6 +410,9 @@ fn watcher_loop(app: AppHandle, state: Arc<AppState>) {
             }
 
             if phase == "ChampSelect" {
+                if let Ok(mut p) = state.assigned_position.lock() {
+                    *p = fetch_local_assigned_position(&client);
+                }
                 let champ_id = fetch_local_champion_id(&client);
                 if let Some(id) = champ_id {
                     if let Ok(mut c) = state.champion_id.lock() {
@@ -170,6 +420,7 @@ fn watcher_loop(app: AppHandle, state: Arc<AppState>) {
                     }
                     let _ = app.emit("lcu:champion-selected", id);
                 }
+                emit_focus(&app, &state);
                 try_auto_apply(&app, &state, &client, champ_id);
             }
         } else if phase == "ChampSelect" {
@@ -188,14 +439,23 @@ fn watcher_loop(app: AppHandle, state: Arc<AppState>) {
                     .unwrap_or(false);
                 if changed {
                     let _ = app.emit("lcu:champion-selected", champ_id);
+                    emit_focus(&app, &state);
                 }
             }
         }
 
         if phase == "InProgress" {
             sample_live_cs(&state);
+            emit_objective_timers(&app, &state);
+            if last_players_emit.elapsed() >= LIVE_PLAYERS_INTERVAL {
+                if let Some(players) = live_client::fetch_live_players() {
+                    let _ = app.emit("live:players", &players);
+                }
+                last_players_emit = std::time::Instant::now();
+            }
             if last_draft_emit.elapsed() >= Duration::from_secs(30) {
                 refresh_live_draft(&app, &state);
+                persist_live_state(&app, &state);
                 last_draft_emit = std::time::Instant::now();
             }
         }
@@ -3,7 +3,10 @@
 mod app_config;
 mod checklist;
 mod commands;
+mod db;
+mod goals;
 mod progression;
+mod rank;
 mod image_cache;
 mod import_bridge;
 mod live_client;
@@ -13,11 +16,15 @@ mod state;
 
 use app_config::{load_companion_config, save_companion_config, CompanionConfig};
 use commands::{
-    apply_build, check_internet_connection, companion_import_build, delete_checklist,
-    get_checklist_draft, get_checklist_history, get_lcu_status, get_pending_build, get_progression,
+    apply_build, backfill_checklist_history, check_internet_connection, companion_import_build, delete_checklist,
+    get_checklist_draft, get_checklist_history, get_checklist_timeline, get_lcu_status, get_lp_history, get_pending_build, get_progression,
     load_progression, resolve_champion_id, save_checklist, save_progression,
     confirm_internet_for_progression, test_internet_for_progression,
-    update_checklist,
+    get_ranked_queues, rebuild_checklists_from_archive, refresh_checklist_from_timeline,
+    update_checklist, get_checklist_rules, set_checklist_rules, reset_checklist_rules,
+    get_storage_status, query_checklist_history, get_checklist_analytics,
+    export_checklist_history, import_checklist_history, get_goals, save_goal, delete_goal,
+    get_focus, get_play_sessions, update_checklist_draft, reevaluate_checklist_history,
 };
 use image_cache::ImageCacheState;
 use lcu::watcher;
@@ -26,6 +33,7 @@ use state::AppState;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::WebviewWindowBuilder;
+use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
@@ -301,6 +309,14 @@ pub fn run() {
         .manage(image_cache)
         .manage(app_state)
         .setup(move |app| {
+            // Before the watcher starts saving; stores fail with this error until it is fixed.
+            if let Err(e) = db::init() {
+                app.dialog()
+                    .message(format!("The companion database could not be opened: {e}"))
+                    .kind(MessageDialogKind::Error)
+                    .title("Companion storage")
+                    .show(|_| {});
+            }
             import_bridge::start(app.handle().clone());
             watcher::start(app.handle().clone(), watcher_state);
 
@@ -361,9 +377,30 @@ pub fn run() {
             clear_image_cache,
             get_checklist_history,
             get_checklist_draft,
+            get_checklist_timeline,
             save_checklist,
             update_checklist,
             delete_checklist,
+            backfill_checklist_history,
+            refresh_checklist_from_timeline,
+            get_ranked_queues,
+            get_lp_history,
+            rebuild_checklists_from_archive,
+            get_checklist_rules,
+            set_checklist_rules,
+            reset_checklist_rules,
+            get_storage_status,
+            query_checklist_history,
+            get_checklist_analytics,
+            export_checklist_history,
+            import_checklist_history,
+            get_goals,
+            save_goal,
+            delete_goal,
+            get_focus,
+            get_play_sessions,
+            update_checklist_draft,
+            reevaluate_checklist_history,
             get_progression,
             load_progression,
             test_internet_for_progression,
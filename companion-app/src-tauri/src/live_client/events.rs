@@ -0,0 +1,364 @@
+//! Live Client event feed (`/liveclientdata/eventdata`) → game timeline.
+
+use super::{active_player_with, get_json, http_client, PlayerIdentity};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum TimelineEventKind {
+    Kill,
+    Death,
+    Assist,
+    FirstBlood,
+    Dragon,
+    Herald,
+    Baron,
+    Turret,
+    Inhibitor,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineEvent {
+    pub event_id: u64,
+    pub game_time_seconds: f64,
+    pub kind: TimelineEventKind,
+    #[serde(default)]
+    pub killer: String,
+    #[serde(default)]
+    pub victim: String,
+    #[serde(default)]
+    pub assisters: Vec<String>,
+    /// Elemental type for dragons (e.g. `Fire`, `Elder`).
+    #[serde(default)]
+    pub dragon_type: Option<String>,
+    #[serde(default)]
+    pub stolen: bool,
+    /// Destroyed structure id (e.g. `Turret_T2_L_03_A`).
+    #[serde(default)]
+    pub structure: Option<String>,
+    /// True when the local player's team took it; `None` if teams are unknown.
+    #[serde(default)]
+    pub ally: Option<bool>,
+}
+
+/// Incremental event collector for the game in progress.
+#[derive(Debug, Clone, Default)]
+pub struct LiveTimeline {
+    pub events: Vec<TimelineEvent>,
+    next_event_id: u64,
+    local: Option<PlayerIdentity>,
+    /// Full Riot ID (or summoner name) → `ORDER` / `CHAOS`, plus the bare game name when no
+    /// other player shares it.
+    teams: HashMap<String, String>,
+}
+
+fn str_of(v: &Value, key: &str) -> String {
+    v.get(key)
+        .and_then(|x| x.as_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+fn bool_of(v: &Value, key: &str) -> bool {
+    match v.get(key) {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::String(s)) => s.eq_ignore_ascii_case("true"),
+        _ => false,
+    }
+}
+
+/// `Turret_T1_…` / `Barracks_T1_…` belong to ORDER, `T2` to CHAOS.
+fn structure_team(structure: &str) -> Option<&'static str> {
+    match structure.split('_').nth(1) {
+        Some("T1") => Some("ORDER"),
+        Some("T2") => Some("CHAOS"),
+        _ => None,
+    }
+}
+
+impl LiveTimeline {
+    pub fn set_players(&mut self, local: PlayerIdentity, teams: HashMap<String, String>) {
+        self.local = Some(local);
+        self.teams = teams;
+    }
+
+    pub fn next_event_id(&self) -> u64 {
+        self.next_event_id
+    }
+
+    fn local_team(&self) -> Option<&str> {
+        self.local
+            .as_ref()?
+            .aliases()
+            .iter()
+            .find_map(|name| self.teams.get(name))
+            .map(String::as_str)
+    }
+
+    /// Whether `name` from the event feed is the local player. A bare game name only counts
+    /// when no other player in the game has it.
+    fn is_local(&self, name: &str) -> bool {
+        let Some(local) = &self.local else {
+            return false;
+        };
+        if local
+            .riot_id()
+            .is_some_and(|id| id.eq_ignore_ascii_case(name))
+        {
+            return true;
+        }
+        local.matches_name(name) && (self.teams.is_empty() || self.teams.contains_key(name))
+    }
+
+    fn is_ally(&self, player: &str) -> Option<bool> {
+        let local = self.local_team()?;
+        self.teams.get(player).map(|t| t == local)
+    }
+
+    /// Append events from an `eventdata` payload that were not seen yet.
+    pub fn ingest(&mut self, payload: &Value) {
+        let events = payload
+            .get("Events")
+            .and_then(|e| e.as_array())
+            .or_else(|| payload.as_array());
+        let Some(events) = events else {
+            return;
+        };
+        for raw in events {
+            let Some(id) = raw.get("EventID").and_then(|x| x.as_u64()) else {
+                continue;
+            };
+            if id < self.next_event_id {
+                continue;
+            }
+            self.next_event_id = id + 1;
+            if let Some(event) = self.classify(id, raw) {
+                self.events.push(event);
+            }
+        }
+    }
+
+    fn classify(&self, event_id: u64, raw: &Value) -> Option<TimelineEvent> {
+        let name = raw.get("EventName").and_then(|x| x.as_str())?;
+        let killer = str_of(raw, "KillerName");
+        let mut event = TimelineEvent {
+            event_id,
+            game_time_seconds: raw.get("EventTime").and_then(|x| x.as_f64()).unwrap_or(0.0),
+            kind: TimelineEventKind::Kill,
+            killer: killer.clone(),
+            victim: String::new(),
+            assisters: raw
+                .get("Assisters")
+                .and_then(|a| a.as_array())
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|x| x.as_str().map(str::to_string))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            dragon_type: None,
+            stolen: bool_of(raw, "Stolen"),
+            structure: None,
+            ally: self.is_ally(&killer),
+        };
+
+        match name {
+            "ChampionKill" => {
+                self.local.as_ref()?;
+                event.victim = str_of(raw, "VictimName");
+                event.kind = if self.is_local(&event.killer) {
+                    TimelineEventKind::Kill
+                } else if self.is_local(&event.victim) {
+                    TimelineEventKind::Death
+                } else if event.assisters.iter().any(|a| self.is_local(a)) {
+                    TimelineEventKind::Assist
+                } else {
+                    return None;
+                };
+            }
+            "FirstBlood" => {
+                event.kind = TimelineEventKind::FirstBlood;
+                event.killer = str_of(raw, "Recipient");
+                event.ally = self.is_ally(&event.killer);
+            }
+            "DragonKill" => {
+                event.kind = TimelineEventKind::Dragon;
+                event.dragon_type = Some(str_of(raw, "DragonType")).filter(|s| !s.is_empty());
+            }
+            "HeraldKill" => event.kind = TimelineEventKind::Herald,
+            "BaronKill" => event.kind = TimelineEventKind::Baron,
+            "TurretKilled" | "InhibKilled" => {
+                event.kind = if name == "TurretKilled" {
+                    TimelineEventKind::Turret
+                } else {
+                    TimelineEventKind::Inhibitor
+                };
+                let structure = str_of(raw, name);
+                event.ally = match (structure_team(&structure), self.local_team()) {
+                    (Some(owner), Some(local)) => Some(owner != local),
+                    _ => None,
+                };
+                event.structure = Some(structure);
+            }
+            _ => return None,
+        }
+        Some(event)
+    }
+}
+
+/// Teams of a `playerlist` payload. Several players can share a game name (`Name#KR1`,
+/// `Name#EUW`), so the bare name is only a key when it is unique.
+fn team_map(players: &[Value]) -> HashMap<String, String> {
+    let players: Vec<(PlayerIdentity, String)> = players
+        .iter()
+        .filter_map(|p| {
+            let id = serde_json::from_value::<PlayerIdentity>(p.clone()).ok()?;
+            Some((id, str_of(p, "team"))).filter(|(_, team)| !team.is_empty())
+        })
+        .collect();
+    let mut teams = HashMap::new();
+    for (id, team) in &players {
+        let game_name = &id.riot_id_game_name;
+        let shared = players
+            .iter()
+            .filter(|(other, _)| other.riot_id_game_name.eq_ignore_ascii_case(game_name))
+            .count()
+            > 1;
+        for name in id.aliases() {
+            if !(shared && &name == game_name) {
+                teams.insert(name, team.clone());
+            }
+        }
+    }
+    teams
+}
+
+fn fetch_teams(client: &reqwest::blocking::Client) -> HashMap<String, String> {
+    let Ok(list) = get_json(client, "/liveclientdata/playerlist") else {
+        return HashMap::new();
+    };
+    team_map(list.as_array().map(Vec::as_slice).unwrap_or_default())
+}
+
+/// Fetch events newer than the last seen `EventID` and append them to the timeline.
+pub fn poll_events(timeline: &mut LiveTimeline) {
+    let Ok(client) = http_client() else {
+        return;
+    };
+    if timeline.teams.is_empty() {
+        let Some(me) = active_player_with(&client) else {
+            return;
+        };
+        let teams = fetch_teams(&client);
+        timeline.set_players(me, teams);
+    }
+    let path = format!(
+        "/liveclientdata/eventdata?eventID={}",
+        timeline.next_event_id()
+    );
+    if let Ok(payload) = get_json(&client, &path) {
+        timeline.ingest(&payload);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeline() -> LiveTimeline {
+        let mut t = LiveTimeline::default();
+        let teams = [("Me", "ORDER"), ("Ally", "ORDER"), ("Enemy", "CHAOS")]
+            .into_iter()
+            .map(|(n, t)| (n.to_string(), t.to_string()))
+            .collect();
+        let me = PlayerIdentity {
+            riot_id: "Me#EUW".into(),
+            riot_id_game_name: "Me".into(),
+            riot_id_tag_line: "EUW".into(),
+            ..Default::default()
+        };
+        t.set_players(me, teams);
+        t
+    }
+
+    #[test]
+    fn classifies_player_and_objective_events() {
+        let payload: Value = serde_json::from_str(
+            r#"{ "Events": [
+                { "EventID": 0, "EventName": "GameStart", "EventTime": 0.03 },
+                { "EventID": 1, "EventName": "ChampionKill", "EventTime": 462.1,
+                  "KillerName": "Enemy", "VictimName": "Me", "Assisters": [] },
+                { "EventID": 2, "EventName": "FirstBlood", "EventTime": 462.1, "Recipient": "Enemy" },
+                { "EventID": 3, "EventName": "ChampionKill", "EventTime": 500.0,
+                  "KillerName": "Ally", "VictimName": "Enemy", "Assisters": ["Me"] },
+                { "EventID": 4, "EventName": "DragonKill", "EventTime": 610.5, "DragonType": "Fire",
+                  "Stolen": "False", "KillerName": "Enemy", "Assisters": [] },
+                { "EventID": 5, "EventName": "TurretKilled", "EventTime": 840.0,
+                  "TurretKilled": "Turret_T2_L_03_A", "KillerName": "Ally", "Assisters": [] }
+            ] }"#,
+        )
+        .unwrap();
+        let mut t = timeline();
+        t.ingest(&payload);
+
+        let kinds: Vec<_> = t.events.iter().map(|e| e.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                TimelineEventKind::Death,
+                TimelineEventKind::FirstBlood,
+                TimelineEventKind::Assist,
+                TimelineEventKind::Dragon,
+                TimelineEventKind::Turret,
+            ]
+        );
+        assert_eq!(t.events[3].dragon_type.as_deref(), Some("Fire"));
+        assert_eq!(t.events[3].ally, Some(false));
+        assert_eq!(t.events[4].ally, Some(true));
+        assert_eq!(t.next_event_id(), 6);
+    }
+
+    #[test]
+    fn tells_apart_players_sharing_a_game_name() {
+        let list: Value =
+            serde_json::from_str(include_str!("fixtures/playerlist_riot_id.json")).unwrap();
+        let teams = team_map(list.as_array().unwrap());
+        assert_eq!(teams["Lelanation#KR1"], "CHAOS");
+        assert_eq!(teams["Lelanation#EUW"], "ORDER");
+        assert!(!teams.contains_key("Lelanation"));
+
+        let me: PlayerIdentity =
+            serde_json::from_str(include_str!("fixtures/activeplayer_riot_id.json")).unwrap();
+        let mut t = LiveTimeline::default();
+        t.set_players(me, teams);
+        let payload: Value = serde_json::from_str(
+            r#"[
+                { "EventID": 1, "EventName": "ChampionKill", "EventTime": 300.0,
+                  "KillerName": "Lelanation#KR1", "VictimName": "Lelanation#EUW", "Assisters": [] },
+                { "EventID": 2, "EventName": "ChampionKill", "EventTime": 400.0,
+                  "KillerName": "Lelanation", "VictimName": "Someone", "Assisters": [] }
+            ]"#,
+        )
+        .unwrap();
+        t.ingest(&payload);
+        assert_eq!(t.events.len(), 1);
+        assert_eq!(t.events[0].kind, TimelineEventKind::Death);
+        assert_eq!(t.events[0].ally, Some(false));
+    }
+
+    #[test]
+    fn ignores_already_seen_events() {
+        let payload: Value = serde_json::from_str(
+            r#"[{ "EventID": 7, "EventName": "BaronKill", "EventTime": 1300.0, "KillerName": "Ally" }]"#,
+        )
+        .unwrap();
+        let mut t = timeline();
+        t.ingest(&payload);
+        t.ingest(&payload);
+        assert_eq!(t.events.len(), 1);
+        assert_eq!(t.events[0].kind, TimelineEventKind::Baron);
+    }
+}
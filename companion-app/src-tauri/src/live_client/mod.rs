@@ -1,23 +1,148 @@
 //! Live Client Data API (port 2999) — CS sampling during an active game.
 
+mod events;
+mod objectives;
+mod players;
+
 use crate::postgame::PostGameStats;
 use reqwest::blocking::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+pub use events::{poll_events, LiveTimeline, TimelineEvent};
+pub use objectives::{objective_timers, respawn_rules};
+pub use players::fetch_live_players;
+
 const LIVE_CLIENT_BASE: &str = "https://127.0.0.1:2999";
 
+/// Samples further apart than this around a milestone are not interpolated.
+const MAX_INTERPOLATION_GAP_SECONDS: f64 = 180.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CsSample {
+    pub game_time: f64,
+    pub cs: u32,
+}
+
+/// CS samples collected while the game is in progress (ordered by game time).
 #[derive(Debug, Clone, Default)]
 pub struct LiveCsSnapshot {
-    pub cs_at_5: Option<u32>,
-    pub cs_at_10: Option<u32>,
+    pub samples: Vec<CsSample>,
+}
+
+impl LiveCsSnapshot {
+    pub fn record(&mut self, game_time: f64, cs: u32) {
+        if let Some(last) = self.samples.last() {
+            if game_time < last.game_time {
+                // Game time went backwards: a new game started without a phase change.
+                self.samples.clear();
+            } else if game_time == last.game_time {
+                return;
+            }
+        }
+        self.samples.push(CsSample { game_time, cs });
+    }
+
+    /// CS at `minute`, interpolated between the nearest samples before and after the mark.
+    pub fn cs_at_minute(&self, minute: u32) -> Option<u32> {
+        interpolate_cs(&self.samples, f64::from(minute) * 60.0)
+    }
+
+    /// Resolved milestones (minute → CS); marks not yet bracketed by samples are omitted.
+    pub fn milestones(&self, minutes: &[u32]) -> BTreeMap<u32, u32> {
+        minutes
+            .iter()
+            .filter_map(|&m| self.cs_at_minute(m).map(|cs| (m, cs)))
+            .collect()
+    }
+}
+
+fn interpolate_cs(samples: &[CsSample], mark: f64) -> Option<u32> {
+    let after_idx = samples.iter().position(|s| s.game_time >= mark)?;
+    let after = samples[after_idx];
+    if after.game_time == mark {
+        return Some(after.cs);
+    }
+    let before = samples[..after_idx].last()?;
+    if after.game_time - before.game_time > MAX_INTERPOLATION_GAP_SECONDS {
+        return None;
+    }
+    let ratio = (mark - before.game_time) / (after.game_time - before.game_time);
+    let cs = f64::from(before.cs) + (f64::from(after.cs) - f64::from(before.cs)) * ratio;
+    Some(cs.round().max(0.0) as u32)
+}
+
+/// Player names as exposed by `activeplayer` / `playerlist` (Riot ID and legacy summoner name).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerIdentity {
+    #[serde(default)]
+    pub riot_id: String,
+    #[serde(default)]
+    pub riot_id_game_name: String,
+    #[serde(default)]
+    pub riot_id_tag_line: String,
+    /// Deprecated by Riot; holds the Riot ID on recent clients.
+    #[serde(default)]
+    pub summoner_name: String,
 }
 
-#[derive(Debug, Deserialize)]
-struct ActivePlayer {
-    #[serde(rename = "summonerName")]
-    summoner_name: String,
+impl PlayerIdentity {
+    /// `GameName#TAG`, built from its parts when `riotId` is missing.
+    pub fn riot_id(&self) -> Option<String> {
+        if !self.riot_id.is_empty() {
+            return Some(self.riot_id.clone());
+        }
+        if self.riot_id_game_name.is_empty() {
+            return None;
+        }
+        if self.riot_id_tag_line.is_empty() {
+            return Some(self.riot_id_game_name.clone());
+        }
+        Some(format!(
+            "{}#{}",
+            self.riot_id_game_name, self.riot_id_tag_line
+        ))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.riot_id().is_none() && self.summoner_name.is_empty()
+    }
+
+    /// Every name this player may appear under (event feeds use the game name only).
+    pub fn aliases(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        for name in [
+            self.riot_id().unwrap_or_default(),
+            self.riot_id_game_name.clone(),
+            self.summoner_name.clone(),
+        ] {
+            if !name.is_empty() && !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+
+    /// Any alias, the bare game name included; with the player list at hand, check that the
+    /// game name is not shared (see `LiveTimeline`).
+    pub fn matches_name(&self, name: &str) -> bool {
+        !name.is_empty()
+            && self
+                .aliases()
+                .iter()
+                .any(|a| a.eq_ignore_ascii_case(name))
+    }
+
+    pub fn matches(&self, other: &PlayerIdentity) -> bool {
+        match (self.riot_id(), other.riot_id()) {
+            (Some(a), Some(b)) => a.eq_ignore_ascii_case(&b),
+            _ => other.aliases().iter().any(|n| self.matches_name(n)),
+        }
+    }
 }
 
 fn http_client() -> Result<Client, String> {
@@ -45,15 +170,20 @@ pub fn is_available() -> bool {
     get_json(&client, "/liveclientdata/gamestats").is_ok()
 }
 
-pub fn active_summoner_name() -> Option<String> {
-    let client = http_client().ok()?;
-    let v = get_json(&client, "/liveclientdata/activeplayer").ok()?;
-    if let Ok(p) = serde_json::from_value::<ActivePlayer>(v) {
-        if !p.summoner_name.is_empty() {
-            return Some(p.summoner_name);
-        }
-    }
-    None
+fn active_player_with(client: &Client) -> Option<PlayerIdentity> {
+    let v = get_json(client, "/liveclientdata/activeplayer").ok()?;
+    serde_json::from_value::<PlayerIdentity>(v)
+        .ok()
+        .filter(|p| !p.is_empty())
+}
+
+/// Entry of `/liveclientdata/playerlist` belonging to `me`.
+fn find_player<'a>(players: &'a [Value], me: &PlayerIdentity) -> Option<&'a Value> {
+    players.iter().find(|p| {
+        serde_json::from_value::<PlayerIdentity>((*p).clone())
+            .map(|id| me.matches(&id))
+            .unwrap_or(false)
+    })
 }
 
 pub fn game_time_seconds() -> Option<f64> {
@@ -62,32 +192,53 @@ pub fn game_time_seconds() -> Option<f64> {
     v.get("gameTime").and_then(|t| t.as_f64())
 }
 
-fn player_scores(summoner_name: &str) -> Option<Value> {
+fn playerscores_path(me: &PlayerIdentity) -> String {
+    match me.riot_id() {
+        Some(riot_id) => format!(
+            "/liveclientdata/playerscores?riotId={}",
+            urlencoding_encode(&riot_id)
+        ),
+        None => format!(
+            "/liveclientdata/playerscores?summonerName={}",
+            urlencoding_encode(&me.summoner_name)
+        ),
+    }
+}
+
+/// Scores of the active player, read from the player list (falls back to `playerscores`).
+fn active_player_scores() -> Option<Value> {
     let client = http_client().ok()?;
-    let path = format!(
-        "/liveclientdata/playerscores?summonerName={}",
-        urlencoding_encode(summoner_name)
-    );
-    get_json(&client, &path).ok()
+    let me = active_player_with(&client)?;
+    if let Ok(list) = get_json(&client, "/liveclientdata/playerlist") {
+        let scores = list
+            .as_array()
+            .and_then(|players| find_player(players, &me))
+            .and_then(|p| p.get("scores"));
+        if let Some(scores) = scores {
+            return Some(scores.clone());
+        }
+    }
+    get_json(&client, &playerscores_path(&me)).ok()
 }
 
-fn creep_score_for(summoner_name: &str) -> Option<u32> {
-    player_scores(summoner_name)?
+fn active_creep_score() -> Option<u32> {
+    active_player_scores()?
         .get("creepScore")
         .and_then(|x| x.as_u64())
         .map(|n| n as u32)
 }
 
 /// Partial stats while a game is in progress (Live Client API).
-pub fn fetch_live_stats(cs_snapshot: &LiveCsSnapshot) -> Option<PostGameStats> {
-    let name = active_summoner_name()?;
-    let scores = player_scores(&name)?;
+pub fn fetch_live_stats(
+    cs_snapshot: &LiveCsSnapshot,
+    milestone_minutes: &[u32],
+) -> Option<PostGameStats> {
+    let scores = active_player_scores()?;
     let game_time = game_time_seconds().unwrap_or(0.0) as u32;
 
     Some(PostGameStats {
         game_duration_seconds: game_time,
-        cs_at_5: cs_snapshot.cs_at_5,
-        cs_at_10: cs_snapshot.cs_at_10,
+        cs_milestones: cs_snapshot.milestones(milestone_minutes),
         cs_total: scores
             .get("creepScore")
             .and_then(|x| x.as_u64())
@@ -126,24 +277,16 @@ fn urlencoding_encode(s: &str) -> String {
     out
 }
 
-/// Sample CS at ~5:00 and ~10:00 game time (±20s tolerance).
-pub fn sample_cs_milestones(snapshot: &mut LiveCsSnapshot) {
+/// Record the current CS; milestones are interpolated from these samples.
+pub fn sample_cs(snapshot: &mut LiveCsSnapshot) {
     let Some(game_time) = game_time_seconds() else {
         return;
     };
-    let Some(name) = active_summoner_name() else {
-        return;
-    };
-    let Some(cs) = creep_score_for(&name) else {
+    let Some(cs) = active_creep_score() else {
         return;
     };
 
-    if snapshot.cs_at_5.is_none() && (280.0..=320.0).contains(&game_time) {
-        snapshot.cs_at_5 = Some(cs);
-    }
-    if snapshot.cs_at_10.is_none() && (580.0..=620.0).contains(&game_time) {
-        snapshot.cs_at_10 = Some(cs);
-    }
+    snapshot.record(game_time, cs);
 }
 
 #[cfg(test)]
@@ -154,4 +297,87 @@ mod tests {
     fn url_encode_summoner_name() {
         assert_eq!(urlencoding_encode("Player One"), "Player%20One");
     }
+
+    fn fixture(raw: &str) -> Value {
+        serde_json::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn reads_legacy_active_player() {
+        let me: PlayerIdentity =
+            serde_json::from_value(fixture(include_str!("fixtures/activeplayer_legacy.json")))
+                .unwrap();
+        assert_eq!(me.riot_id(), None);
+        assert_eq!(me.summoner_name, "Player One");
+        assert_eq!(
+            playerscores_path(&me),
+            "/liveclientdata/playerscores?summonerName=Player%20One"
+        );
+
+        let list = fixture(include_str!("fixtures/playerlist_legacy.json"));
+        let player = find_player(list.as_array().unwrap(), &me).unwrap();
+        assert_eq!(player["scores"]["creepScore"], 112);
+    }
+
+    #[test]
+    fn reads_riot_id_active_player() {
+        let me: PlayerIdentity =
+            serde_json::from_value(fixture(include_str!("fixtures/activeplayer_riot_id.json")))
+                .unwrap();
+        assert_eq!(me.riot_id().as_deref(), Some("Lelanation#EUW"));
+        assert_eq!(
+            playerscores_path(&me),
+            "/liveclientdata/playerscores?riotId=Lelanation%23EUW"
+        );
+        assert!(me.matches_name("lelanation"));
+
+        let list = fixture(include_str!("fixtures/playerlist_riot_id.json"));
+        let player = find_player(list.as_array().unwrap(), &me).unwrap();
+        assert_eq!(player["championName"], "Ahri");
+        assert_eq!(player["scores"]["creepScore"], 154);
+    }
+
+    #[test]
+    fn builds_riot_id_from_parts() {
+        let me = PlayerIdentity {
+            riot_id_game_name: "Split Name".into(),
+            riot_id_tag_line: "1234".into(),
+            ..Default::default()
+        };
+        assert_eq!(me.riot_id().as_deref(), Some("Split Name#1234"));
+        let other = PlayerIdentity {
+            riot_id: "split name#1234".into(),
+            ..Default::default()
+        };
+        assert!(me.matches(&other));
+    }
+
+    fn snapshot(samples: &[(f64, u32)]) -> LiveCsSnapshot {
+        let mut snap = LiveCsSnapshot::default();
+        for &(t, cs) in samples {
+            snap.record(t, cs);
+        }
+        snap
+    }
+
+    #[test]
+    fn interpolates_missed_milestone() {
+        let snap = snapshot(&[(270.0, 36), (330.0, 42), (600.0, 80)]);
+        assert_eq!(snap.cs_at_minute(5), Some(39));
+        assert_eq!(snap.cs_at_minute(10), Some(80));
+        assert_eq!(snap.cs_at_minute(15), None);
+    }
+
+    #[test]
+    fn skips_milestone_when_gap_is_too_wide() {
+        let snap = snapshot(&[(100.0, 10), (500.0, 60)]);
+        assert_eq!(snap.cs_at_minute(5), None);
+    }
+
+    #[test]
+    fn resets_samples_when_game_time_goes_back() {
+        let snap = snapshot(&[(590.0, 80), (610.0, 82), (20.0, 0)]);
+        assert_eq!(snap.samples.len(), 1);
+        assert!(snap.milestones(&[5, 10]).is_empty());
+    }
 }
@@ -0,0 +1,314 @@
+//! Dragon / herald / baron / inhibitor respawn timers derived from the live timeline.
+
+use super::events::{TimelineEvent, TimelineEventKind};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Spawn rules in effect from `since_patch` (seconds of game time).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RespawnRules {
+    pub since_patch: (u32, u32),
+    pub dragon_first_spawn: f64,
+    pub dragon_respawn: f64,
+    pub elder_respawn: f64,
+    /// Non-elder dragons a team needs for soul (elder spawns after).
+    pub dragons_for_soul: usize,
+    pub herald_first_spawn: f64,
+    /// `None` when the herald does not respawn after being killed.
+    pub herald_respawn: Option<f64>,
+    pub herald_despawn: f64,
+    pub baron_first_spawn: f64,
+    pub baron_respawn: f64,
+    pub inhibitor_respawn: f64,
+}
+
+/// Ordered by patch; the last entry not newer than the game version applies.
+const RESPAWN_RULES: &[RespawnRules] = &[
+    RespawnRules {
+        since_patch: (13, 1),
+        dragon_first_spawn: 300.0,
+        dragon_respawn: 300.0,
+        elder_respawn: 360.0,
+        dragons_for_soul: 4,
+        herald_first_spawn: 480.0,
+        herald_respawn: Some(360.0),
+        herald_despawn: 1185.0,
+        baron_first_spawn: 1200.0,
+        baron_respawn: 360.0,
+        inhibitor_respawn: 300.0,
+    },
+    RespawnRules {
+        since_patch: (14, 1),
+        dragon_first_spawn: 300.0,
+        dragon_respawn: 300.0,
+        elder_respawn: 360.0,
+        dragons_for_soul: 4,
+        herald_first_spawn: 840.0,
+        herald_respawn: None,
+        herald_despawn: 1185.0,
+        baron_first_spawn: 1200.0,
+        baron_respawn: 360.0,
+        inhibitor_respawn: 300.0,
+    },
+    RespawnRules {
+        since_patch: (25, 1),
+        dragon_first_spawn: 300.0,
+        dragon_respawn: 300.0,
+        elder_respawn: 360.0,
+        dragons_for_soul: 4,
+        herald_first_spawn: 900.0,
+        herald_respawn: None,
+        herald_despawn: 1485.0,
+        baron_first_spawn: 1500.0,
+        baron_respawn: 360.0,
+        inhibitor_respawn: 300.0,
+    },
+];
+
+fn parse_patch(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Rules for a game version such as `14.23.636.1234` (latest rules if unknown).
+pub fn respawn_rules(game_version: &str) -> &'static RespawnRules {
+    let latest = &RESPAWN_RULES[RESPAWN_RULES.len() - 1];
+    let Some(patch) = parse_patch(game_version) else {
+        return latest;
+    };
+    RESPAWN_RULES
+        .iter()
+        .rev()
+        .find(|r| r.since_patch <= patch)
+        .unwrap_or(&RESPAWN_RULES[0])
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveTimer {
+    /// `dragon`, `elder`, `herald` or `baron`.
+    pub objective: String,
+    /// Game time of the next spawn; `None` once it will not spawn again.
+    pub spawn_at_seconds: Option<f64>,
+    pub remaining_seconds: Option<f64>,
+    pub alive: bool,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InhibitorTimer {
+    pub structure: String,
+    /// True when the inhibitor belongs to the enemy team.
+    pub enemy: Option<bool>,
+    pub respawn_at_seconds: f64,
+    pub remaining_seconds: f64,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectiveTimers {
+    pub game_time_seconds: f64,
+    pub dragon: ObjectiveTimer,
+    pub herald: ObjectiveTimer,
+    pub baron: ObjectiveTimer,
+    pub inhibitors: Vec<InhibitorTimer>,
+}
+
+fn timer(objective: &str, spawn_at: Option<f64>, game_time: f64) -> ObjectiveTimer {
+    ObjectiveTimer {
+        objective: objective.to_string(),
+        spawn_at_seconds: spawn_at,
+        remaining_seconds: spawn_at.map(|t| (t - game_time).max(0.0)),
+        alive: spawn_at.is_some_and(|t| t <= game_time),
+    }
+}
+
+fn last_of(events: &[TimelineEvent], kind: TimelineEventKind) -> Option<&TimelineEvent> {
+    events.iter().rev().find(|e| e.kind == kind)
+}
+
+fn is_elder(event: &TimelineEvent) -> bool {
+    event
+        .dragon_type
+        .as_deref()
+        .is_some_and(|t| t.eq_ignore_ascii_case("Elder"))
+}
+
+/// Dragons whose team is unknown count toward both sides, so soul (and then elder) is still
+/// detected when teams cannot be resolved; an elder kill always means soul was claimed.
+fn dragon_timer(events: &[TimelineEvent], rules: &RespawnRules, game_time: f64) -> ObjectiveTimer {
+    let (mut ally, mut enemy, mut unknown) = (0usize, 0usize, 0usize);
+    for e in events
+        .iter()
+        .filter(|e| e.kind == TimelineEventKind::Dragon && !is_elder(e))
+    {
+        match e.ally {
+            Some(true) => ally += 1,
+            Some(false) => enemy += 1,
+            None => unknown += 1,
+        }
+    }
+    let soul_claimed = ally.max(enemy) + unknown >= rules.dragons_for_soul
+        || events
+            .iter()
+            .any(|e| e.kind == TimelineEventKind::Dragon && is_elder(e));
+    let objective = if soul_claimed { "elder" } else { "dragon" };
+    let spawn_at = match last_of(events, TimelineEventKind::Dragon) {
+        Some(last) if soul_claimed => last.game_time_seconds + rules.elder_respawn,
+        Some(last) => last.game_time_seconds + rules.dragon_respawn,
+        None => rules.dragon_first_spawn,
+    };
+    timer(objective, Some(spawn_at), game_time)
+}
+
+fn herald_timer(events: &[TimelineEvent], rules: &RespawnRules, game_time: f64) -> ObjectiveTimer {
+    let spawn_at = match last_of(events, TimelineEventKind::Herald) {
+        Some(last) => rules
+            .herald_respawn
+            .map(|r| last.game_time_seconds + r)
+            .filter(|&t| t < rules.herald_despawn),
+        None => Some(rules.herald_first_spawn),
+    };
+    if game_time >= rules.herald_despawn {
+        return timer("herald", None, game_time);
+    }
+    timer("herald", spawn_at, game_time)
+}
+
+fn baron_timer(events: &[TimelineEvent], rules: &RespawnRules, game_time: f64) -> ObjectiveTimer {
+    let spawn_at = match last_of(events, TimelineEventKind::Baron) {
+        Some(last) => last.game_time_seconds + rules.baron_respawn,
+        None => rules.baron_first_spawn,
+    };
+    timer("baron", Some(spawn_at), game_time)
+}
+
+fn inhibitor_timers(
+    events: &[TimelineEvent],
+    rules: &RespawnRules,
+    game_time: f64,
+) -> Vec<InhibitorTimer> {
+    let mut latest: HashMap<&str, &TimelineEvent> = HashMap::new();
+    for e in events
+        .iter()
+        .filter(|e| e.kind == TimelineEventKind::Inhibitor)
+    {
+        if let Some(structure) = e.structure.as_deref() {
+            latest.insert(structure, e);
+        }
+    }
+    let mut timers: Vec<InhibitorTimer> = latest
+        .into_iter()
+        .filter_map(|(structure, e)| {
+            let respawn_at = e.game_time_seconds + rules.inhibitor_respawn;
+            (respawn_at > game_time).then(|| InhibitorTimer {
+                structure: structure.to_string(),
+                enemy: e.ally,
+                respawn_at_seconds: respawn_at,
+                remaining_seconds: respawn_at - game_time,
+            })
+        })
+        .collect();
+    timers.sort_by(|a, b| a.respawn_at_seconds.total_cmp(&b.respawn_at_seconds));
+    timers
+}
+
+pub fn objective_timers(
+    events: &[TimelineEvent],
+    rules: &RespawnRules,
+    game_time: f64,
+) -> ObjectiveTimers {
+    ObjectiveTimers {
+        game_time_seconds: game_time,
+        dragon: dragon_timer(events, rules, game_time),
+        herald: herald_timer(events, rules, game_time),
+        baron: baron_timer(events, rules, game_time),
+        inhibitors: inhibitor_timers(events, rules, game_time),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: TimelineEventKind, t: f64, ally: bool) -> TimelineEvent {
+        TimelineEvent {
+            event_id: 0,
+            game_time_seconds: t,
+            kind,
+            killer: String::new(),
+            victim: String::new(),
+            assisters: Vec::new(),
+            dragon_type: Some("Fire".into()),
+            stolen: false,
+            structure: None,
+            ally: Some(ally),
+        }
+    }
+
+    #[test]
+    fn picks_rules_by_patch() {
+        assert_eq!(respawn_rules("13.24.550.1234").since_patch, (13, 1));
+        assert_eq!(respawn_rules("14.23.636.1234").since_patch, (14, 1));
+        assert_eq!(respawn_rules("garbage").since_patch, (25, 1));
+    }
+
+    #[test]
+    fn dragon_respawns_five_minutes_after_kill() {
+        let rules = respawn_rules("14.10");
+        let events = vec![event(TimelineEventKind::Dragon, 330.0, true)];
+        let timers = objective_timers(&events, rules, 400.0);
+        assert_eq!(timers.dragon.objective, "dragon");
+        assert_eq!(timers.dragon.spawn_at_seconds, Some(630.0));
+        assert_eq!(timers.dragon.remaining_seconds, Some(230.0));
+        assert!(!timers.dragon.alive);
+        assert!(!timers.baron.alive);
+    }
+
+    #[test]
+    fn elder_follows_soul() {
+        let rules = respawn_rules("14.10");
+        let events: Vec<_> = [330.0, 700.0, 1050.0, 1400.0]
+            .into_iter()
+            .map(|t| event(TimelineEventKind::Dragon, t, false))
+            .collect();
+        let timers = objective_timers(&events, rules, 1500.0);
+        assert_eq!(timers.dragon.objective, "elder");
+        assert_eq!(timers.dragon.spawn_at_seconds, Some(1760.0));
+    }
+
+    #[test]
+    fn elder_follows_soul_when_teams_are_unknown() {
+        let rules = respawn_rules("14.10");
+        let mut events: Vec<_> = [330.0, 700.0, 1050.0]
+            .into_iter()
+            .map(|t| event(TimelineEventKind::Dragon, t, true))
+            .collect();
+        events.iter_mut().for_each(|e| e.ally = None);
+        assert_eq!(
+            objective_timers(&events, rules, 1100.0).dragon.objective,
+            "dragon"
+        );
+        let mut fourth = event(TimelineEventKind::Dragon, 1400.0, true);
+        fourth.ally = None;
+        events.push(fourth);
+        assert_eq!(
+            objective_timers(&events, rules, 1500.0).dragon.objective,
+            "elder"
+        );
+    }
+
+    #[test]
+    fn herald_gone_after_kill_and_inhibitor_tracked() {
+        let rules = respawn_rules("14.10");
+        let mut inhib = event(TimelineEventKind::Inhibitor, 1600.0, true);
+        inhib.structure = Some("Barracks_T2_L1".into());
+        let events = vec![event(TimelineEventKind::Herald, 900.0, true), inhib];
+        let timers = objective_timers(&events, rules, 1700.0);
+        assert_eq!(timers.herald.spawn_at_seconds, None);
+        assert_eq!(timers.inhibitors.len(), 1);
+        assert_eq!(timers.inhibitors[0].remaining_seconds, 200.0);
+    }
+}
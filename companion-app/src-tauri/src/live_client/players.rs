@@ -0,0 +1,184 @@
+//! All ten players from `/liveclientdata/playerlist` (items, spells, level, KDA).
+
+use super::{active_player_with, get_json, http_client, PlayerIdentity};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveItem {
+    #[serde(rename = "itemID", default)]
+    pub item_id: u32,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub count: u32,
+    #[serde(default)]
+    pub slot: u32,
+    #[serde(default)]
+    pub price: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSummonerSpell {
+    #[serde(default)]
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveSummonerSpells {
+    #[serde(default)]
+    pub summoner_spell_one: Option<LiveSummonerSpell>,
+    #[serde(default)]
+    pub summoner_spell_two: Option<LiveSummonerSpell>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveScores {
+    #[serde(default)]
+    pub kills: u32,
+    #[serde(default)]
+    pub deaths: u32,
+    #[serde(default)]
+    pub assists: u32,
+    #[serde(default)]
+    pub creep_score: u32,
+    #[serde(default)]
+    pub ward_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LivePlayer {
+    #[serde(flatten)]
+    pub identity: PlayerIdentity,
+    #[serde(default)]
+    pub champion_name: String,
+    /// `ORDER` or `CHAOS`.
+    #[serde(default)]
+    pub team: String,
+    /// `TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM`, `UTILITY` (empty outside draft modes).
+    #[serde(default)]
+    pub position: String,
+    #[serde(default)]
+    pub level: u32,
+    #[serde(default)]
+    pub is_dead: bool,
+    #[serde(default)]
+    pub respawn_timer: f64,
+    #[serde(default)]
+    pub items: Vec<LiveItem>,
+    #[serde(default)]
+    pub summoner_spells: LiveSummonerSpells,
+    #[serde(default)]
+    pub scores: LiveScores,
+    #[serde(default)]
+    pub is_local: bool,
+}
+
+impl LivePlayer {
+    /// Gold value of the current inventory (sum of item prices).
+    pub fn item_gold(&self) -> u32 {
+        self.items.iter().map(|i| i.price * i.count.max(1)).sum()
+    }
+}
+
+/// Local player vs. the enemy in the same position.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LaneMatchup {
+    pub opponent_champion: String,
+    pub opponent_riot_id: String,
+    pub level_lead: i32,
+    pub item_gold_lead: i32,
+    pub cs_lead: i32,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LivePlayers {
+    pub players: Vec<LivePlayer>,
+    pub lane_matchup: Option<LaneMatchup>,
+}
+
+fn lane_matchup(players: &[LivePlayer]) -> Option<LaneMatchup> {
+    let me = players.iter().find(|p| p.is_local)?;
+    if me.position.is_empty() {
+        return None;
+    }
+    let opponent = players
+        .iter()
+        .find(|p| p.team != me.team && p.position == me.position)?;
+    Some(LaneMatchup {
+        opponent_champion: opponent.champion_name.clone(),
+        opponent_riot_id: opponent.identity.riot_id().unwrap_or_default(),
+        level_lead: me.level as i32 - opponent.level as i32,
+        item_gold_lead: me.item_gold() as i32 - opponent.item_gold() as i32,
+        cs_lead: me.scores.creep_score as i32 - opponent.scores.creep_score as i32,
+    })
+}
+
+/// Parse a `playerlist` payload, flagging the active player.
+pub fn parse_player_list(list: &Value, me: Option<&PlayerIdentity>) -> LivePlayers {
+    let mut players: Vec<LivePlayer> = list
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|p| serde_json::from_value::<LivePlayer>(p.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(me) = me {
+        for p in &mut players {
+            p.is_local = me.matches(&p.identity);
+        }
+    }
+    let lane_matchup = lane_matchup(&players);
+    LivePlayers {
+        players,
+        lane_matchup,
+    }
+}
+
+pub fn fetch_live_players() -> Option<LivePlayers> {
+    let client = http_client().ok()?;
+    let me = active_player_with(&client);
+    let list = get_json(&client, "/liveclientdata/playerlist").ok()?;
+    Some(parse_player_list(&list, me.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lobby_and_lane_matchup() {
+        let me: PlayerIdentity =
+            serde_json::from_str(include_str!("fixtures/activeplayer_riot_id.json")).unwrap();
+        let list: Value =
+            serde_json::from_str(include_str!("fixtures/playerlist_riot_id.json")).unwrap();
+        let lobby = parse_player_list(&list, Some(&me));
+
+        assert_eq!(lobby.players.len(), 2);
+        let local = lobby.players.iter().find(|p| p.is_local).unwrap();
+        assert_eq!(local.champion_name, "Ahri");
+        assert_eq!(local.items.len(), 2);
+        assert_eq!(
+            local
+                .summoner_spells
+                .summoner_spell_one
+                .as_ref()
+                .map(|s| s.display_name.as_str()),
+            Some("Flash")
+        );
+
+        let matchup = lobby.lane_matchup.unwrap();
+        assert_eq!(matchup.opponent_champion, "Syndra");
+        assert_eq!(matchup.level_lead, 0);
+        assert_eq!(matchup.cs_lead, 14);
+        assert_eq!(matchup.item_gold_lead, 600);
+    }
+}
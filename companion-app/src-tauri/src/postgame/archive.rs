@@ -0,0 +1,171 @@
+//! Raw end-of-game / match JSON per game (gzip), so new stats can be computed for past games.
+
+use super::{apply_game_detail, match_history, parse_eog_stats_block, PostGameStats};
+use crate::app_config::load_companion_config;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchivedGame {
+    pub version: u32,
+    pub game_id: u64,
+    pub summoner_id: u64,
+    pub archived_at_ms: i64,
+    /// `/lol-end-of-game/v1/eog-stats-block` (games played with the companion open).
+    #[serde(default)]
+    pub eog_stats_block: Option<Value>,
+    /// `/lol-match-history/v1/games/{gameId}`.
+    #[serde(default)]
+    pub game: Option<Value>,
+}
+
+fn archive_dir() -> PathBuf {
+    crate::db::data_dir().join("archive")
+}
+
+fn game_path(game_id: u64) -> PathBuf {
+    archive_dir().join(format!("{game_id}.json.gz"))
+}
+
+fn encode(game: &ArchivedGame) -> Result<Vec<u8>, String> {
+    let json = serde_json::to_vec(game).map_err(|e| e.to_string())?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&json).map_err(|e| e.to_string())?;
+    encoder.finish().map_err(|e| e.to_string())
+}
+
+fn decode(bytes: &[u8]) -> Result<ArchivedGame, String> {
+    let mut json = Vec::new();
+    GzDecoder::new(bytes)
+        .read_to_end(&mut json)
+        .map_err(|e| format!("Corrupt archive: {e}"))?;
+    serde_json::from_slice(&json).map_err(|e| format!("Invalid archive JSON: {e}"))
+}
+
+pub fn load(game_id: u64) -> Result<ArchivedGame, String> {
+    let bytes = std::fs::read(game_path(game_id)).map_err(|e| e.to_string())?;
+    decode(&bytes)
+}
+
+/// Archive raw payloads of a game, keeping parts stored earlier that are missing now.
+pub fn store(
+    game_id: u64,
+    summoner_id: u64,
+    eog_stats_block: Option<Value>,
+    game: Option<Value>,
+) -> Result<(), String> {
+    let previous = load(game_id).ok();
+    let entry = ArchivedGame {
+        version: ARCHIVE_VERSION,
+        game_id,
+        summoner_id,
+        archived_at_ms: crate::checklist::evaluator::now_millis(),
+        eog_stats_block: eog_stats_block
+            .or_else(|| previous.as_ref().and_then(|p| p.eog_stats_block.clone())),
+        game: game.or_else(|| previous.and_then(|p| p.game)),
+    };
+    std::fs::create_dir_all(archive_dir()).map_err(|e| e.to_string())?;
+    std::fs::write(game_path(game_id), encode(&entry)?).map_err(|e| e.to_string())?;
+    prune(load_companion_config().archive_retention_days);
+    Ok(())
+}
+
+/// Game ids of every archived game.
+pub fn list() -> Vec<u64> {
+    let Ok(dir) = std::fs::read_dir(archive_dir()) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<u64> = dir
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            e.file_name()
+                .to_str()?
+                .strip_suffix(".json.gz")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// Delete archives older than `retention_days` (`None` keeps everything).
+pub fn prune(retention_days: Option<u32>) -> u32 {
+    let Some(days) = retention_days else {
+        return 0;
+    };
+    let max_age = Duration::from_secs(u64::from(days) * 24 * 3600);
+    let now = SystemTime::now();
+    let mut removed = 0;
+    for game_id in list() {
+        let path = game_path(game_id);
+        let expired = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok())
+            .is_some_and(|age| age > max_age);
+        if expired && std::fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+/// Re-parse an archived game with the current parsers.
+pub fn rebuild_stats(archived: &ArchivedGame) -> Result<PostGameStats, String> {
+    if let Some(eog) = &archived.eog_stats_block {
+        let mut stats = parse_eog_stats_block(&eog.to_string(), archived.summoner_id)?;
+        stats.game_id = Some(archived.game_id);
+        if let Some(game) = &archived.game {
+            apply_game_detail(&mut stats, game);
+        }
+        return Ok(stats);
+    }
+    let game = archived
+        .game
+        .as_ref()
+        .ok_or_else(|| "Archive has no game data".to_string())?;
+    match_history::parse_match_game(game, archived.summoner_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_rebuilds_from_match_json() {
+        let game: Value = serde_json::from_str(
+            r#"{
+                "gameId": 42, "gameDuration": 1500, "gameMode": "CLASSIC", "queueId": 420,
+                "participantIdentities": [{ "participantId": 1, "player": { "summonerId": 7 } }],
+                "participants": [{ "participantId": 1, "teamId": 100, "championId": 1,
+                    "stats": { "win": true, "kills": 4, "totalMinionsKilled": 150 } }],
+                "teams": [{ "teamId": 100, "win": "Win" }]
+            }"#,
+        )
+        .unwrap();
+        let archived = ArchivedGame {
+            version: ARCHIVE_VERSION,
+            game_id: 42,
+            summoner_id: 7,
+            archived_at_ms: 1,
+            eog_stats_block: None,
+            game: Some(game),
+        };
+        let back = decode(&encode(&archived).unwrap()).unwrap();
+        let stats = rebuild_stats(&back).unwrap();
+        assert_eq!(stats.game_id, Some(42));
+        assert_eq!(stats.kills, 4);
+        assert_eq!(stats.cs_total, 150);
+        assert!(rebuild_stats(&ArchivedGame::default()).is_err());
+    }
+}
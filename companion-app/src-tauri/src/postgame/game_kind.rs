@@ -0,0 +1,109 @@
+//! Classify a finished game (map mode and how it ended) for checklist scoring.
+
+use super::PostGameStats;
+use serde::{Deserialize, Serialize};
+
+/// Any game shorter than this is treated as a remake, flag or not.
+const REMAKE_MAX_SECONDS: u32 = 240;
+/// Remake votes open at 3:00 and close a little after.
+const REMAKE_VOTE_MAX_SECONDS: u32 = 300;
+/// Surrenders before 20:00 (the 15-minute vote) count as early.
+const EARLY_SURRENDER_MAX_SECONDS: u32 = 20 * 60;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum GameMode {
+    #[default]
+    SummonersRift,
+    Aram,
+    Arena,
+    /// Rotating or custom modes (URF, One for All, …).
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum GameEnding {
+    #[default]
+    Normal,
+    Remake,
+    EarlySurrender,
+    Leaver,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GameClass {
+    pub mode: GameMode,
+    pub ending: GameEnding,
+}
+
+pub fn game_mode(stats: &PostGameStats) -> GameMode {
+    match (
+        stats.game_mode.to_ascii_uppercase().as_str(),
+        stats.queue_id,
+    ) {
+        ("ARAM", _) | (_, Some(450 | 100 | 720)) => GameMode::Aram,
+        ("CHERRY", _) | (_, Some(1700 | 1710)) => GameMode::Arena,
+        ("CLASSIC" | "", _) => GameMode::SummonersRift,
+        _ => GameMode::Other,
+    }
+}
+
+pub fn game_ending(stats: &PostGameStats) -> GameEnding {
+    let duration = stats.game_duration_seconds;
+    if (duration > 0 && duration < REMAKE_MAX_SECONDS)
+        || (stats.ended_in_early_surrender && duration <= REMAKE_VOTE_MAX_SECONDS)
+    {
+        return GameEnding::Remake;
+    }
+    if stats.leaver {
+        return GameEnding::Leaver;
+    }
+    if (stats.ended_in_surrender || stats.ended_in_early_surrender)
+        && duration < EARLY_SURRENDER_MAX_SECONDS
+    {
+        return GameEnding::EarlySurrender;
+    }
+    GameEnding::Normal
+}
+
+pub fn classify(stats: &PostGameStats) -> GameClass {
+    GameClass {
+        mode: game_mode(stats),
+        ending: game_ending(stats),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(mode: &str, queue_id: u32, seconds: u32) -> PostGameStats {
+        PostGameStats {
+            game_mode: mode.into(),
+            queue_id: Some(queue_id),
+            game_duration_seconds: seconds,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn classifies_modes_and_endings() {
+        assert_eq!(classify(&stats("CLASSIC", 420, 1800)), GameClass::default());
+        assert_eq!(game_mode(&stats("ARAM", 450, 1200)), GameMode::Aram);
+        assert_eq!(game_mode(&stats("CHERRY", 1700, 900)), GameMode::Arena);
+        assert_eq!(game_mode(&stats("URF", 900, 900)), GameMode::Other);
+
+        assert_eq!(game_ending(&stats("CLASSIC", 420, 200)), GameEnding::Remake);
+        let mut remake = stats("CLASSIC", 420, 290);
+        remake.ended_in_early_surrender = true;
+        assert_eq!(game_ending(&remake), GameEnding::Remake);
+
+        let mut ff15 = stats("CLASSIC", 420, 16 * 60);
+        ff15.ended_in_surrender = true;
+        assert_eq!(game_ending(&ff15), GameEnding::EarlySurrender);
+        ff15.game_duration_seconds = 25 * 60;
+        assert_eq!(game_ending(&ff15), GameEnding::Normal);
+    }
+}
@@ -0,0 +1,162 @@
+//! Per-minute CS / gold / XP from LCU `/lol-match-history/v1/game-timelines/{gameId}`.
+
+use super::{match_history, PostGameStats};
+use crate::lcu::LcuClient;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MinuteFrame {
+    pub minute: u32,
+    pub cs: u32,
+    pub gold: u32,
+    pub xp: u32,
+    pub level: u32,
+}
+
+fn u32_of(v: &Value, key: &str) -> u32 {
+    v.get(key).and_then(|x| x.as_u64()).unwrap_or(0) as u32
+}
+
+/// Frames of `participant_id`, one per minute (frames are ~60 s apart).
+pub fn parse_game_timeline(timeline: &Value, participant_id: u64) -> Vec<MinuteFrame> {
+    let Some(frames) = timeline.get("frames").and_then(|f| f.as_array()) else {
+        return Vec::new();
+    };
+    let key = participant_id.to_string();
+    let mut out: Vec<MinuteFrame> = frames
+        .iter()
+        .filter_map(|frame| {
+            let timestamp = frame.get("timestamp").and_then(|x| x.as_u64())?;
+            let p = frame.get("participantFrames")?.get(&key)?;
+            Some(MinuteFrame {
+                minute: ((timestamp as f64) / 60_000.0).round() as u32,
+                cs: u32_of(p, "minionsKilled") + u32_of(p, "jungleMinionsKilled"),
+                gold: u32_of(p, "totalGold"),
+                xp: u32_of(p, "xp"),
+                level: u32_of(p, "level"),
+            })
+        })
+        .collect();
+    out.dedup_by_key(|f| f.minute);
+    out
+}
+
+/// Fill CS milestones missing from live sampling; returns true if any was added.
+pub fn fill_missing_milestones(
+    stats: &mut PostGameStats,
+    frames: &[MinuteFrame],
+    minutes: &[u32],
+) -> bool {
+    let mut changed = false;
+    for &minute in minutes {
+        if stats.cs_milestones.contains_key(&minute) {
+            continue;
+        }
+        if let Some(frame) = frames.iter().find(|f| f.minute == minute) {
+            stats.cs_milestones.insert(minute, frame.cs);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Single attempt for a known `participantId` (match-history games are already loaded).
+pub fn fetch_game_timeline(
+    client: &LcuClient,
+    game_id: u64,
+    participant_id: u64,
+) -> Result<Vec<MinuteFrame>, String> {
+    let raw = client.get(&format!("/lol-match-history/v1/game-timelines/{game_id}"))?;
+    let timeline: Value =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid game timeline JSON: {e}"))?;
+    let frames = parse_game_timeline(&timeline, participant_id);
+    if frames.is_empty() {
+        return Err("Game timeline has no frames yet".into());
+    }
+    Ok(frames)
+}
+
+fn fetch_frames_once(
+    client: &LcuClient,
+    game_id: u64,
+    summoner_id: u64,
+) -> Result<Vec<MinuteFrame>, String> {
+    let game = match_history::fetch_match_game(client, game_id)?;
+    let participant_id = match_history::local_participant_id(&game, summoner_id)
+        .ok_or_else(|| "Local player not found in match history game".to_string())?;
+    fetch_game_timeline(client, game_id, participant_id)
+}
+
+/// The timeline is published a little after the game ends, so retry a few times.
+pub fn fetch_minute_frames(
+    client: &LcuClient,
+    game_id: u64,
+    summoner_id: u64,
+    attempts: u32,
+) -> Result<Vec<MinuteFrame>, String> {
+    let mut last_err = String::from("Game timeline not ready");
+    for attempt in 0..attempts.max(1) {
+        if attempt > 0 {
+            thread::sleep(Duration::from_secs(3));
+        }
+        match fetch_frames_once(client, game_id, summoner_id) {
+            Ok(frames) => return Ok(frames),
+            Err(e) => last_err = e,
+        }
+    }
+    Err(last_err)
+}
+
+/// Attach minute frames to `stats` and fill missing CS milestones from them.
+pub fn enrich_from_timeline(
+    client: &LcuClient,
+    summoner_id: u64,
+    stats: &mut PostGameStats,
+    minutes: &[u32],
+) -> Result<bool, String> {
+    let game_id = stats
+        .game_id
+        .ok_or_else(|| "Game id unknown, cannot fetch timeline".to_string())?;
+    let missing = minutes.iter().any(|m| stats.cs_at(*m).is_none());
+    let frames = fetch_minute_frames(client, game_id, summoner_id, if missing { 4 } else { 1 })?;
+    let changed = fill_missing_milestones(stats, &frames, minutes);
+    stats.minute_frames = frames;
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMELINE: &str = r#"{
+        "frameInterval": 60000,
+        "frames": [
+            { "timestamp": 0, "participantFrames": {
+                "3": { "participantId": 3, "minionsKilled": 0, "jungleMinionsKilled": 0, "totalGold": 500, "xp": 0, "level": 1 } } },
+            { "timestamp": 300412, "participantFrames": {
+                "3": { "participantId": 3, "minionsKilled": 38, "jungleMinionsKilled": 4, "totalGold": 2100, "xp": 1800, "level": 5 } } },
+            { "timestamp": 600371, "participantFrames": {
+                "3": { "participantId": 3, "minionsKilled": 79, "jungleMinionsKilled": 8, "totalGold": 4300, "xp": 4200, "level": 9 } } }
+        ]
+    }"#;
+
+    #[test]
+    fn fills_only_missing_milestones() {
+        let timeline: Value = serde_json::from_str(TIMELINE).unwrap();
+        let frames = parse_game_timeline(&timeline, 3);
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].minute, 5);
+        assert_eq!(frames[2].gold, 4300);
+
+        let mut stats = PostGameStats::default();
+        stats.cs_milestones.insert(5, 40);
+        assert!(fill_missing_milestones(&mut stats, &frames, &[5, 10, 15]));
+        assert_eq!(stats.cs_at(5), Some(40));
+        assert_eq!(stats.cs_at(10), Some(87));
+        assert_eq!(stats.cs_at(15), None);
+    }
+}
@@ -0,0 +1,253 @@
+//! Past games from LCU `/lol-match-history/v1` converted to `PostGameStats`.
+
+use super::{
+    apply_game_detail, parse_participant, parse_player_stats, player_riot_id, str_field,
+    PostGameParticipant, PostGameStats,
+};
+use crate::lcu::LcuClient;
+use serde_json::Value;
+
+/// Summary of a match-history entry (newest first).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchSummary {
+    pub game_id: u64,
+    pub game_creation_ms: i64,
+}
+
+pub fn parse_match_page(raw: &str) -> Result<Vec<MatchSummary>, String> {
+    let v: Value =
+        serde_json::from_str(raw).map_err(|e| format!("Invalid match history JSON: {e}"))?;
+    let games = v
+        .get("games")
+        .and_then(|g| g.get("games").or(Some(g)))
+        .and_then(|g| g.as_array())
+        .or_else(|| v.as_array());
+    let Some(games) = games else {
+        return Ok(Vec::new());
+    };
+    Ok(games
+        .iter()
+        .filter_map(|g| {
+            Some(MatchSummary {
+                game_id: g.get("gameId")?.as_u64()?,
+                game_creation_ms: g.get("gameCreation").and_then(|x| x.as_i64()).unwrap_or(0),
+            })
+        })
+        .collect())
+}
+
+/// One page of the current summoner's match history (`begIndex..endIndex`).
+pub fn fetch_match_page(
+    client: &LcuClient,
+    beg_index: u32,
+    end_index: u32,
+) -> Result<Vec<MatchSummary>, String> {
+    let path = format!(
+        "/lol-match-history/v1/products/lol/current-summoner/matches?begIndex={beg_index}&endIndex={end_index}"
+    );
+    parse_match_page(&client.get(&path)?)
+}
+
+/// Game end time in epoch ms (`gameCreation` + `gameDuration`).
+pub fn game_end_ms(game: &Value) -> i64 {
+    let creation = game
+        .get("gameCreation")
+        .and_then(|x| x.as_i64())
+        .unwrap_or(0);
+    let duration = game
+        .get("gameDuration")
+        .and_then(|x| x.as_i64())
+        .unwrap_or(0);
+    creation + duration * 1000
+}
+
+/// `timeline.lane` / `timeline.role` of match history v1 → team position.
+fn timeline_position(participant: &Value) -> String {
+    let timeline = participant.get("timeline").unwrap_or(&Value::Null);
+    let lane = str_field(timeline, &["lane"]).to_ascii_uppercase();
+    let role = str_field(timeline, &["role"]).to_ascii_uppercase();
+    match lane.as_str() {
+        "TOP" | "JUNGLE" => lane,
+        "MID" | "MIDDLE" => "MIDDLE".into(),
+        "BOT" | "BOTTOM" if role.contains("SUPPORT") => "UTILITY".into(),
+        "BOT" | "BOTTOM" => "BOTTOM".into(),
+        _ => String::new(),
+    }
+}
+
+fn identity_of(game: &Value, participant_id: u64) -> Option<&Value> {
+    game.get("participantIdentities")?
+        .as_array()?
+        .iter()
+        .find(|i| i.get("participantId").and_then(|x| x.as_u64()) == Some(participant_id))?
+        .get("player")
+}
+
+/// `participantId` of `summoner_id` in a match-history game.
+pub fn local_participant_id(game: &Value, summoner_id: u64) -> Option<u64> {
+    game.get("participantIdentities")?
+        .as_array()?
+        .iter()
+        .find(|i| {
+            i.get("player")
+                .and_then(|p| p.get("summonerId"))
+                .and_then(|x| x.as_u64())
+                == Some(summoner_id)
+        })?
+        .get("participantId")?
+        .as_u64()
+}
+
+fn team_won(game: &Value, team_id: Option<u32>) -> bool {
+    let Some(teams) = game.get("teams").and_then(|t| t.as_array()) else {
+        return false;
+    };
+    teams
+        .iter()
+        .find(|t| t.get("teamId").and_then(|x| x.as_u64()).map(|n| n as u32) == team_id)
+        .and_then(|t| t.get("win"))
+        .map(|w| w.as_bool().unwrap_or_else(|| w.as_str() == Some("Win")))
+        .unwrap_or(false)
+}
+
+fn team_stat(game: &Value, team_id: Option<u32>, key: &str) -> u32 {
+    game.get("teams")
+        .and_then(|t| t.as_array())
+        .and_then(|teams| {
+            teams
+                .iter()
+                .find(|t| t.get("teamId").and_then(|x| x.as_u64()).map(|n| n as u32) == team_id)
+        })
+        .and_then(|t| t.get(key))
+        .and_then(|x| x.as_u64())
+        .unwrap_or(0) as u32
+}
+
+fn participant_from(game: &Value, raw: &Value) -> PostGameParticipant {
+    let team_id = raw.get("teamId").and_then(|x| x.as_u64()).map(|n| n as u32);
+    let won = raw
+        .get("stats")
+        .and_then(|s| s.get("win"))
+        .and_then(|x| x.as_bool())
+        .unwrap_or_else(|| team_won(game, team_id));
+    let mut p = parse_participant(raw, team_id, won);
+    p.win = won;
+    p.position = timeline_position(raw);
+    if let Some(identity) = raw
+        .get("participantId")
+        .and_then(|x| x.as_u64())
+        .and_then(|id| identity_of(game, id))
+    {
+        p.summoner_id = identity.get("summonerId").and_then(|x| x.as_u64());
+        p.riot_id = player_riot_id(identity);
+    }
+    p
+}
+
+/// Convert `/lol-match-history/v1/games/{gameId}` into stats for `summoner_id`.
+pub fn parse_match_game(game: &Value, summoner_id: u64) -> Result<PostGameStats, String> {
+    let raw_participants = game
+        .get("participants")
+        .and_then(|p| p.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let mut participants = Vec::new();
+    let mut local = None;
+    for raw in raw_participants {
+        let mut p = participant_from(game, raw);
+        p.is_local = p.summoner_id == Some(summoner_id);
+        if p.is_local {
+            local = Some((raw, p.clone()));
+        }
+        participants.push(p);
+    }
+    let (raw_me, me) =
+        local.ok_or_else(|| "Local player not found in match history game".to_string())?;
+
+    let mut stats = parse_player_stats(raw_me, me.win);
+    stats.game_id = game.get("gameId").and_then(|x| x.as_u64());
+    stats.game_duration_seconds = game
+        .get("gameDuration")
+        .and_then(|x| x.as_u64())
+        .unwrap_or(0) as u32;
+    stats.win = me.win;
+    stats.team_dragon_kills = team_stat(game, me.team_id, "dragonKills");
+    stats.team_baron_kills = team_stat(game, me.team_id, "baronKills");
+    stats.position = me.position.clone();
+    stats.participants = participants;
+    apply_game_detail(&mut stats, game);
+    Ok(stats)
+}
+
+pub fn fetch_match_game(client: &LcuClient, game_id: u64) -> Result<Value, String> {
+    let raw = client.get(&format!("/lol-match-history/v1/games/{game_id}"))?;
+    serde_json::from_str(&raw).map_err(|e| format!("Invalid match JSON: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: &str = r#"{
+        "gameId": 7001, "gameCreation": 1700000000000, "gameDuration": 1800,
+        "gameMode": "CLASSIC", "gameType": "MATCHED_GAME", "queueId": 420,
+        "participantIdentities": [
+            { "participantId": 1, "player": { "summonerId": 11, "gameName": "Me", "tagLine": "EUW" } },
+            { "participantId": 2, "player": { "summonerId": 22, "gameName": "Them", "tagLine": "EUW" } }
+        ],
+        "participants": [
+            { "participantId": 1, "teamId": 100, "championId": 103,
+              "timeline": { "lane": "MIDDLE", "role": "SOLO" },
+              "stats": { "win": true, "kills": 7, "deaths": 3, "assists": 9,
+                         "totalMinionsKilled": 190, "neutralMinionsKilled": 12,
+                         "goldEarned": 12800, "wardsPlaced": 14, "wardsKilled": 4,
+                         "visionWardsBoughtInGame": 3, "visionScore": 31,
+                         "totalDamageDealtToChampions": 24000, "item0": 3089, "item1": 0 } },
+            { "participantId": 2, "teamId": 200, "championId": 7,
+              "timeline": { "lane": "BOTTOM", "role": "DUO_SUPPORT" },
+              "stats": { "win": false, "kills": 1, "deaths": 8 } }
+        ],
+        "teams": [
+            { "teamId": 100, "win": "Win", "dragonKills": 3, "baronKills": 1 },
+            { "teamId": 200, "win": "Fail", "dragonKills": 1, "baronKills": 0 }
+        ]
+    }"#;
+
+    #[test]
+    fn converts_match_history_game() {
+        let game: Value = serde_json::from_str(GAME).unwrap();
+        let stats = parse_match_game(&game, 11).unwrap();
+        assert_eq!(stats.game_id, Some(7001));
+        assert_eq!(stats.game_duration_seconds, 1800);
+        assert_eq!(stats.cs_total, 202);
+        assert_eq!(stats.kills, 7);
+        assert_eq!(stats.vision_wards_bought, 3);
+        assert_eq!(stats.team_dragon_kills, 3);
+        assert_eq!(stats.team_baron_kills, 1);
+        assert_eq!(stats.queue_id, Some(420));
+        assert!(stats.win);
+
+        let me = stats.local_participant().unwrap();
+        assert_eq!(me.riot_id, "Me#EUW");
+        assert_eq!(me.position, "MIDDLE");
+        assert_eq!(stats.position, "MIDDLE");
+        assert_eq!(stats.vision_score, 31);
+        assert_eq!(stats.jungle_cs, 12);
+        assert_eq!(me.items, vec![3089]);
+        assert_eq!(stats.participants[1].position, "UTILITY");
+        assert_eq!(game_end_ms(&game), 1_700_000_000_000 + 1_800_000);
+        assert_eq!(local_participant_id(&game, 22), Some(2));
+    }
+
+    #[test]
+    fn parses_match_page_envelope() {
+        let page = parse_match_page(
+            r#"{ "games": { "gameCount": 2, "games": [
+                { "gameId": 2, "gameCreation": 200 }, { "gameId": 1, "gameCreation": 100 }
+            ] } }"#,
+        )
+        .unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0].game_id, 2);
+    }
+}
@@ -1,8 +1,17 @@
 //! Post-game stats from LCU `/lol-end-of-game/v1/eog-stats-block`.
 
+pub mod archive;
+pub mod game_kind;
+pub mod game_timeline;
+pub mod match_history;
+pub mod role;
+
 use crate::lcu::LcuClient;
+use crate::rank;
+use game_timeline::MinuteFrame;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::thread;
 use std::time::Duration;
 
@@ -11,8 +20,9 @@ use std::time::Duration;
 pub struct PostGameStats {
     pub game_id: Option<u64>,
     pub game_duration_seconds: u32,
-    pub cs_at_5: Option<u32>,
-    pub cs_at_10: Option<u32>,
+    /// CS per game minute (e.g. `5 → 44`).
+    #[serde(flatten, with = "cs_milestones_json")]
+    pub cs_milestones: BTreeMap<u32, u32>,
     pub cs_total: u32,
     pub gold_earned: u32,
     pub kills: u32,
@@ -49,9 +59,153 @@ pub struct PostGameStats {
     pub enemy_team_avg_elo: Option<i32>,
     #[serde(default)]
     pub game_avg_elo: Option<i32>,
+    /// Every player of the game (both teams), local player included.
+    #[serde(default)]
+    pub participants: Vec<PostGameParticipant>,
+    /// Local player's per-minute CS / gold / XP from the LCU game timeline.
+    #[serde(default)]
+    pub minute_frames: Vec<MinuteFrame>,
+    /// Remake vote (`gameEndedInEarlySurrender`).
+    #[serde(default)]
+    pub ended_in_early_surrender: bool,
+    #[serde(default)]
+    pub ended_in_surrender: bool,
+    /// Local player flagged as leaver / AFK.
+    #[serde(default)]
+    pub leaver: bool,
+    /// `TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM` or `UTILITY` (match data, else champ select).
+    #[serde(default)]
+    pub position: String,
+    #[serde(default)]
+    pub vision_score: u32,
+    /// Neutral monsters (part of `cs_total`).
+    #[serde(default)]
+    pub jungle_cs: u32,
+    /// Dragons and barons the local player took.
+    #[serde(default)]
+    pub epic_monster_kills: u32,
 }
 
-fn local_summoner_id(client: &LcuClient) -> Option<u64> {
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PostGameParticipant {
+    #[serde(default)]
+    pub summoner_id: Option<u64>,
+    /// `GameName#TAG`, or the legacy summoner name.
+    #[serde(default)]
+    pub riot_id: String,
+    pub champion_id: u32,
+    #[serde(default)]
+    pub team_id: Option<u32>,
+    /// `TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM`, `UTILITY` or empty.
+    #[serde(default)]
+    pub position: String,
+    #[serde(default)]
+    pub is_local: bool,
+    pub win: bool,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub cs: u32,
+    pub gold_earned: u32,
+    pub damage_to_champions: u32,
+    pub damage_taken: u32,
+    pub vision_score: u32,
+    pub wards_placed: u32,
+    pub wards_killed: u32,
+    #[serde(default)]
+    pub items: Vec<u32>,
+}
+
+impl PostGameStats {
+    pub fn cs_at(&self, minute: u32) -> Option<u32> {
+        self.cs_milestones.get(&minute).copied()
+    }
+
+    pub fn local_participant(&self) -> Option<&PostGameParticipant> {
+        self.participants.iter().find(|p| p.is_local)
+    }
+
+    /// Enemy in the same position as the local player.
+    pub fn lane_opponent(&self) -> Option<&PostGameParticipant> {
+        let me = self.local_participant()?;
+        if me.position.is_empty() {
+            return None;
+        }
+        self.participants
+            .iter()
+            .find(|p| p.team_id != me.team_id && p.position == me.position)
+    }
+
+    /// Average of `value` over the local player's team (`ally`) or the enemy team.
+    pub fn team_average(
+        &self,
+        ally: bool,
+        value: impl Fn(&PostGameParticipant) -> f32,
+    ) -> Option<f32> {
+        let local_team = self.local_participant()?.team_id;
+        let values: Vec<f32> = self
+            .participants
+            .iter()
+            .filter(|p| (p.team_id == local_team) == ally)
+            .map(value)
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        Some(values.iter().sum::<f32>() / values.len() as f32)
+    }
+}
+
+/// `csMilestones` object plus the legacy `csAt5` / `csAt10` fields older saves and UIs use.
+mod cs_milestones_json {
+    use serde::ser::SerializeMap;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S: Serializer>(
+        milestones: &BTreeMap<u32, u32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("csMilestones", milestones)?;
+        map.serialize_entry("csAt5", &milestones.get(&5))?;
+        map.serialize_entry("csAt10", &milestones.get(&10))?;
+        map.end()
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Repr {
+        // String keys: integer map keys are not supported inside `#[serde(flatten)]`.
+        #[serde(default)]
+        cs_milestones: BTreeMap<String, u32>,
+        #[serde(default)]
+        cs_at_5: Option<u32>,
+        #[serde(default)]
+        cs_at_10: Option<u32>,
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<u32, u32>, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+        let mut milestones: BTreeMap<u32, u32> = repr
+            .cs_milestones
+            .iter()
+            .filter_map(|(k, v)| k.parse::<u32>().ok().map(|m| (m, *v)))
+            .collect();
+        if let Some(cs) = repr.cs_at_5 {
+            milestones.entry(5).or_insert(cs);
+        }
+        if let Some(cs) = repr.cs_at_10 {
+            milestones.entry(10).or_insert(cs);
+        }
+        Ok(milestones)
+    }
+}
+
+pub fn local_summoner_id(client: &LcuClient) -> Option<u64> {
     let raw = client.get("/lol-login/v1/session").ok()?;
     let v: Value = serde_json::from_str(&raw).ok()?;
     v.get("summonerId").and_then(|x| x.as_u64())
@@ -71,6 +225,16 @@ fn stat_u32(stats: &Value, keys: &[&str]) -> u32 {
     0
 }
 
+/// True for `true`, a non-zero number or `"1"` / `"true"` under any of `keys`.
+fn flag(v: &Value, keys: &[&str]) -> bool {
+    keys.iter().any(|key| match v.get(*key) {
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_f64().is_some_and(|n| n != 0.0),
+        Some(Value::String(s)) => s == "1" || s.eq_ignore_ascii_case("true"),
+        _ => false,
+    })
+}
+
 fn i32_from(v: &Value, keys: &[&str]) -> Option<i32> {
     for key in keys {
         if let Some(n) = v.get(*key).and_then(|x| x.as_i64()) {
@@ -125,7 +289,15 @@ fn parse_player_stats(player: &Value, team_won: bool) -> PostGameStats {
         .or_else(|| player.get("playerStats"))
         .unwrap_or(player);
 
-    let cs_minions = stat_u32(stats, &["TOTAL_MINIONS_KILLED", "minionsKilled", "cs"]);
+    let cs_minions = stat_u32(
+        stats,
+        &[
+            "TOTAL_MINIONS_KILLED",
+            "minionsKilled",
+            "totalMinionsKilled",
+            "cs",
+        ],
+    );
     let cs_jungle = stat_u32(
         stats,
         &[
@@ -165,6 +337,16 @@ fn parse_player_stats(player: &Value, team_won: bool) -> PostGameStats {
             .get("win")
             .and_then(|x| x.as_bool())
             .unwrap_or(team_won),
+        ended_in_early_surrender: flag(
+            stats,
+            &["GAME_ENDED_IN_EARLY_SURRENDER", "gameEndedInEarlySurrender"],
+        ),
+        ended_in_surrender: flag(stats, &["GAME_ENDED_IN_SURRENDER", "gameEndedInSurrender"]),
+        leaver: flag(player, &["leaver", "LEAVER"]) || flag(stats, &["LEAVER", "leaver"]),
+        vision_score: stat_u32(stats, &["VISION_SCORE", "visionScore"]),
+        jungle_cs: cs_jungle,
+        epic_monster_kills: stat_u32(stats, &["DRAGON_KILLS", "dragonKills"])
+            + stat_u32(stats, &["BARON_KILLS", "baronKills"]),
         ..Default::default()
     }
 }
@@ -264,6 +446,120 @@ fn collect_team_elos(root: &Value, local_team: Option<u32>) -> (Vec<i32>, Vec<i3
     (ally, enemy, all)
 }
 
+fn player_riot_id(player: &Value) -> String {
+    let game_name = str_field(player, &["riotIdGameName", "gameName"]);
+    let tag = str_field(player, &["riotIdTagLine", "tagLine"]);
+    if !game_name.is_empty() && !tag.is_empty() {
+        return format!("{game_name}#{tag}");
+    }
+    if !game_name.is_empty() {
+        return game_name;
+    }
+    str_field(player, &["riotId", "summonerName"])
+}
+
+fn player_position(player: &Value) -> String {
+    role::normalize_position(&str_field(
+        player,
+        &[
+            "selectedPosition",
+            "detectedTeamPosition",
+            "teamPosition",
+            "position",
+        ],
+    ))
+}
+
+fn player_items(player: &Value, stats: &Value) -> Vec<u32> {
+    if let Some(items) = player.get("items").and_then(|i| i.as_array()) {
+        return items
+            .iter()
+            .filter_map(|x| x.as_u64())
+            .map(|n| n as u32)
+            .filter(|&id| id > 0)
+            .collect();
+    }
+    (0..7)
+        .map(|slot| stat_u32(stats, &[&format!("ITEM{slot}"), &format!("item{slot}")]))
+        .filter(|&id| id > 0)
+        .collect()
+}
+
+fn parse_participant(player: &Value, team_id: Option<u32>, team_won: bool) -> PostGameParticipant {
+    let base = parse_player_stats(player, team_won);
+    let stats = player
+        .get("stats")
+        .or_else(|| player.get("playerStats"))
+        .unwrap_or(player);
+    PostGameParticipant {
+        summoner_id: player_summoner_id(player),
+        riot_id: player_riot_id(player),
+        champion_id: base.champion_id,
+        team_id: team_id.or_else(|| {
+            player
+                .get("team")
+                .or_else(|| player.get("teamId"))
+                .and_then(|x| x.as_u64())
+                .map(|n| n as u32)
+        }),
+        position: player_position(player),
+        is_local: false,
+        win: base.win,
+        kills: base.kills,
+        deaths: base.deaths,
+        assists: base.assists,
+        cs: base.cs_total,
+        gold_earned: base.gold_earned,
+        damage_to_champions: stat_u32(
+            stats,
+            &[
+                "TOTAL_DAMAGE_DEALT_TO_CHAMPIONS",
+                "totalDamageDealtToChampions",
+            ],
+        ),
+        damage_taken: stat_u32(stats, &["TOTAL_DAMAGE_TAKEN", "totalDamageTaken"]),
+        vision_score: stat_u32(stats, &["VISION_SCORE", "visionScore"]),
+        wards_placed: base.wards_placed,
+        wards_killed: base.wards_killed,
+        items: player_items(player, stats),
+    }
+}
+
+/// All players of the EOG block, from `teams[].players` or a flat `players` list.
+fn collect_participants(root: &Value, summoner_id: u64) -> Vec<PostGameParticipant> {
+    let mut participants = Vec::new();
+    if let Some(teams) = root.get("teams").and_then(|t| t.as_array()) {
+        for team in teams {
+            let team_id = team
+                .get("teamId")
+                .or_else(|| team.get("TEAM"))
+                .and_then(|x| x.as_u64())
+                .map(|n| n as u32);
+            let team_won = team
+                .get("isWinningTeam")
+                .or_else(|| team.get("win"))
+                .and_then(|x| x.as_bool())
+                .unwrap_or(false);
+            if let Some(players) = team.get("players").and_then(|p| p.as_array()) {
+                for player in players {
+                    participants.push(parse_participant(player, team_id, team_won));
+                }
+            }
+        }
+    }
+    if participants.is_empty() {
+        if let Some(players) = root.get("players").and_then(|p| p.as_array()) {
+            for player in players {
+                participants.push(parse_participant(player, None, false));
+            }
+        }
+    }
+    for p in &mut participants {
+        p.is_local = p.summoner_id == Some(summoner_id);
+    }
+    participants
+}
+
 fn apply_eog_metadata(root: &Value, player: &Value, stats: &mut PostGameStats) {
     if stats.game_duration_seconds == 0 {
         stats.game_duration_seconds = root
@@ -289,6 +585,8 @@ fn apply_eog_metadata(root: &Value, player: &Value, stats: &mut PostGameStats) {
         .unwrap_or("")
         .to_string();
     stats.ranked = root.get("ranked").and_then(|x| x.as_bool()).unwrap_or(false);
+    stats.ended_in_early_surrender |= flag(root, &["gameEndedInEarlySurrender"]);
+    stats.ended_in_surrender |= flag(root, &["gameEndedInSurrender"]);
 
     stats.lp_change = i32_from(root, &["eloChange", "leaguePointsDelta"]);
     stats.player_elo = i32_from(root, &["elo"]);
@@ -311,70 +609,6 @@ fn apply_eog_metadata(root: &Value, player: &Value, stats: &mut PostGameStats) {
     stats.game_avg_elo = avg_i32(&all);
 }
 
-fn format_rank_tier(tier: &str, division: &str) -> String {
-    if tier.is_empty() || tier.eq_ignore_ascii_case("UNRANKED") {
-        return String::new();
-    }
-    let tier_lower = tier.to_ascii_lowercase();
-    let tier_fmt = tier_lower
-        .chars()
-        .next()
-        .map(|c| c.to_uppercase().collect::<String>())
-        .unwrap_or_default()
-        + &tier_lower.chars().skip(1).collect::<String>();
-    if division.is_empty() {
-        tier_fmt
-    } else {
-        format!("{tier_fmt} {division}")
-    }
-}
-
-fn fetch_player_rank(client: &LcuClient, summoner_id: u64) -> (String, Option<i32>) {
-    let path = format!("/lol-ranked/v1/ranked-overview/{summoner_id}");
-    let raw = match client.get(&path) {
-        Ok(r) => r,
-        Err(_) => return (String::new(), None),
-    };
-    let v: Value = match serde_json::from_str(&raw) {
-        Ok(v) => v,
-        Err(_) => return (String::new(), None),
-    };
-
-    let queues = v
-        .get("queues")
-        .and_then(|q| q.as_array())
-        .or_else(|| v.as_array());
-
-    let Some(queues) = queues else {
-        return (String::new(), None);
-    };
-
-    for q in queues {
-        let qt = q
-            .get("queueType")
-            .and_then(|x| x.as_str())
-            .unwrap_or("");
-        if qt != "RANKED_SOLO_5x5" {
-            continue;
-        }
-        let tier = q.get("tier").and_then(|x| x.as_str()).unwrap_or("");
-        let division = q
-            .get("division")
-            .or_else(|| q.get("rank"))
-            .and_then(|x| x.as_str())
-            .unwrap_or("");
-        let lp = q
-            .get("leaguePoints")
-            .and_then(|x| x.as_i64())
-            .map(|n| n as i32);
-        let rank = format_rank_tier(tier, division);
-        if !rank.is_empty() {
-            return (rank, lp);
-        }
-    }
-    (String::new(), None)
-}
-
 fn str_field(v: &Value, keys: &[&str]) -> String {
     for key in keys {
         if let Some(s) = v.get(*key).and_then(|x| x.as_str()) {
@@ -414,17 +648,24 @@ fn fetch_game_detail(client: &LcuClient, game_id: u64) -> Option<Value> {
     serde_json::from_str(&raw).ok()
 }
 
-fn enrich_from_lcu(client: &LcuClient, summoner_id: u64, stats: &mut PostGameStats) {
-    let (rank, lp) = fetch_player_rank(client, summoner_id);
-    if !rank.is_empty() {
-        stats.player_rank = rank;
-        stats.player_lp = lp;
+/// Adds match detail and rank; returns the raw match detail for archiving.
+fn enrich_from_lcu(
+    client: &LcuClient,
+    summoner_id: u64,
+    stats: &mut PostGameStats,
+) -> Option<Value> {
+    let game = stats.game_id.and_then(|gid| fetch_game_detail(client, gid));
+    if let Some(game) = &game {
+        apply_game_detail(stats, game);
     }
-    if let Some(gid) = stats.game_id {
-        if let Some(game) = fetch_game_detail(client, gid) {
-            apply_game_detail(stats, &game);
+    if let Ok(queues) = rank::fetch_ranked_queues(client, summoner_id) {
+        let game_queue = rank::queue_type_of_game(&stats.queue_type, stats.queue_id);
+        if let Some(q) = rank::rank_for_game(&queues, game_queue.as_deref()) {
+            stats.player_rank = q.display();
+            stats.player_lp = Some(q.league_points);
         }
     }
+    game
 }
 
 /// Parse `/lol-end-of-game/v1/eog-stats-block` for the logged-in summoner.
@@ -455,6 +696,8 @@ pub fn parse_eog_stats_block(raw: &str, summoner_id: u64) -> Result<PostGameStat
             "TEAM_BARON_KILLS",
         ));
     apply_eog_metadata(&root, player, &mut stats);
+    stats.position = player_position(player);
+    stats.participants = collect_participants(&root, summoner_id);
 
     Ok(stats)
 }
@@ -470,8 +713,9 @@ fn fetch_latest_game_summary(client: &LcuClient) -> Option<(Value, u64)> {
     Some((game.clone(), id))
 }
 
-/// Fetch post-game stats with retries (EOG screen may load slowly).
-pub fn fetch_postgame_stats(client: &LcuClient) -> Result<PostGameStats, String> {
+/// Fetch post-game stats with retries (EOG screen may load slowly), with the error of the
+/// raw-payload archive write if it failed.
+pub fn fetch_postgame_stats(client: &LcuClient) -> Result<(PostGameStats, Option<String>), String> {
     let summoner_id =
         local_summoner_id(client).ok_or_else(|| "Cannot resolve summoner id".to_string())?;
 
@@ -488,8 +732,12 @@ pub fn fetch_postgame_stats(client: &LcuClient) -> Result<PostGameStats, String>
                             stats.game_id = Some(game_id);
                             apply_game_detail(&mut stats, &summary);
                         }
-                        enrich_from_lcu(client, summoner_id, &mut stats);
-                        return Ok(stats);
+                        let game = enrich_from_lcu(client, summoner_id, &mut stats);
+                        let archive_error = stats.game_id.and_then(|game_id| {
+                            let eog = serde_json::from_str(&raw).ok();
+                            archive::store(game_id, summoner_id, eog, game).err()
+                        });
+                        return Ok((stats, archive_error));
                     }
                     Err(e) => last_err = e,
                 }
@@ -574,4 +822,78 @@ mod tests {
         assert_eq!(stats.enemy_team_avg_elo, Some(1260));
         assert_eq!(stats.game_avg_elo, Some(1225));
     }
+
+    #[test]
+    fn parses_all_participants() {
+        let raw = r#"{
+            "gameLength": 1500,
+            "localPlayer": { "summonerId": 1 },
+            "teams": [{
+                "teamId": 100,
+                "isWinningTeam": true,
+                "players": [
+                    { "summonerId": 1, "championId": 103, "riotIdGameName": "Me", "riotIdTagLine": "EUW",
+                      "selectedPosition": "MIDDLE", "items": [3802, 1056, 0],
+                      "stats": { "CHAMPIONS_KILLED": 6, "NUM_DEATHS": 2, "ASSISTS": 4,
+                                 "MINIONS_KILLED": 0, "TOTAL_MINIONS_KILLED": 180,
+                                 "TOTAL_DAMAGE_DEALT_TO_CHAMPIONS": 21000, "TOTAL_DAMAGE_TAKEN": 15000,
+                                 "VISION_SCORE": 18, "GOLD_EARNED": 11000 } },
+                    { "summonerId": 2, "championId": 412, "summonerName": "Support Main",
+                      "selectedPosition": "UTILITY",
+                      "stats": { "VISION_SCORE": 60, "GOLD_EARNED": 7000, "ITEM0": 3853 } }
+                ]
+            }, {
+                "teamId": 200,
+                "isWinningTeam": false,
+                "players": [
+                    { "summonerId": 3, "championId": 134, "riotIdGameName": "Them", "riotIdTagLine": "NA1",
+                      "selectedPosition": "MIDDLE",
+                      "stats": { "TOTAL_MINIONS_KILLED": 150, "GOLD_EARNED": 9000, "VISION_SCORE": 12 } },
+                    { "summonerId": 4, "championId": 64, "selectedPosition": "JUNGLE",
+                      "stats": { "GOLD_EARNED": 8000 } }
+                ]
+            }]
+        }"#;
+        let stats = parse_eog_stats_block(raw, 1).unwrap();
+        assert_eq!(stats.participants.len(), 4);
+
+        let me = stats.local_participant().unwrap();
+        assert_eq!(me.riot_id, "Me#EUW");
+        assert_eq!(me.items, vec![3802, 1056]);
+        assert_eq!(me.damage_to_champions, 21000);
+        assert!(me.win);
+
+        let support = &stats.participants[1];
+        assert_eq!(support.riot_id, "Support Main");
+        assert_eq!(support.items, vec![3853]);
+
+        let opponent = stats.lane_opponent().unwrap();
+        assert_eq!(opponent.riot_id, "Them#NA1");
+        assert_eq!(opponent.team_id, Some(200));
+        assert!(!opponent.win);
+
+        assert_eq!(stats.team_average(true, |p| p.vision_score as f32), Some(39.0));
+        assert_eq!(stats.team_average(false, |p| p.gold_earned as f32), Some(8500.0));
+    }
+
+    #[test]
+    fn legacy_cs_fields_round_trip_into_milestones() {
+        let legacy = r#"{
+            "gameDurationSeconds": 1500, "csAt5": 41, "csAt10": 83, "csTotal": 200,
+            "goldEarned": 0, "kills": 0, "deaths": 0, "assists": 0, "wardsPlaced": 0,
+            "visionWardsBought": 0, "wardsKilled": 0, "championId": 1, "win": false,
+            "teamDragonKills": 0, "teamBaronKills": 0
+        }"#;
+        let stats: PostGameStats = serde_json::from_str(legacy).unwrap();
+        assert_eq!(stats.cs_at(5), Some(41));
+        assert_eq!(stats.cs_at(10), Some(83));
+
+        let mut stats = stats;
+        stats.cs_milestones.insert(15, 120);
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["csAt5"], 41);
+        assert_eq!(json["csMilestones"]["15"], 120);
+        let back: PostGameStats = serde_json::from_value(json).unwrap();
+        assert_eq!(back.cs_milestones, stats.cs_milestones);
+    }
 }
@@ -0,0 +1,73 @@
+//! Position played in a game, used to pick the checklist rule set.
+
+use super::PostGameStats;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum Role {
+    Top,
+    Jungle,
+    Middle,
+    Bottom,
+    Support,
+    #[default]
+    Unknown,
+}
+
+/// LCU / match positions (`TOP`, `jungle`, `MID`, `UTILITY`, `SUPPORT`, …) → team position.
+pub fn normalize_position(raw: &str) -> String {
+    let raw = raw.trim().to_ascii_uppercase();
+    match raw.as_str() {
+        "MID" => "MIDDLE".into(),
+        "BOT" | "ADC" => "BOTTOM".into(),
+        "SUPPORT" => "UTILITY".into(),
+        "TOP" | "JUNGLE" | "MIDDLE" | "BOTTOM" | "UTILITY" => raw,
+        _ => String::new(),
+    }
+}
+
+pub fn role_of_position(position: &str) -> Role {
+    match normalize_position(position).as_str() {
+        "TOP" => Role::Top,
+        "JUNGLE" => Role::Jungle,
+        "MIDDLE" => Role::Middle,
+        "BOTTOM" => Role::Bottom,
+        "UTILITY" => Role::Support,
+        _ => Role::Unknown,
+    }
+}
+
+/// Stored position, else a jungler guess from a mostly-neutral CS total.
+pub fn detect_role(stats: &PostGameStats) -> Role {
+    match role_of_position(&stats.position) {
+        Role::Unknown if stats.jungle_cs > 0 && stats.jungle_cs * 2 > stats.cs_total => {
+            Role::Jungle
+        }
+        role => role,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_role_from_position_or_jungle_cs() {
+        assert_eq!(role_of_position("utility"), Role::Support);
+        assert_eq!(role_of_position("MID"), Role::Middle);
+        assert_eq!(role_of_position(""), Role::Unknown);
+
+        let jungler = PostGameStats {
+            cs_total: 180,
+            jungle_cs: 140,
+            ..Default::default()
+        };
+        assert_eq!(detect_role(&jungler), Role::Jungle);
+        let top = PostGameStats {
+            position: "TOP".into(),
+            ..jungler
+        };
+        assert_eq!(detect_role(&top), Role::Top);
+    }
+}
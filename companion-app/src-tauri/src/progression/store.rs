@@ -1,40 +1,35 @@
 use super::ProgressionSave;
-use std::path::PathBuf;
+use crate::db::{self, sql_err};
+use rusqlite::{Connection, OptionalExtension};
 
-fn data_dir() -> PathBuf {
-    #[cfg(target_os = "windows")]
-    {
-        let appdata = std::env::var("APPDATA").unwrap_or_else(|_| ".".into());
-        PathBuf::from(appdata).join("Lelanation").join("Companion")
-    }
-    #[cfg(not(target_os = "windows"))]
-    {
-        let home = std::env::var("HOME").unwrap_or_else(|_| ".".into());
-        PathBuf::from(home)
-            .join(".config")
-            .join("lelanation-companion")
-    }
-}
-
-fn progression_path() -> PathBuf {
-    data_dir().join("progression.json")
+pub(crate) fn save_in(conn: &Connection, data: &ProgressionSave) -> Result<(), String> {
+    let raw = serde_json::to_string(data).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO progression (id, data) VALUES (1, ?1)
+         ON CONFLICT (id) DO UPDATE SET data = excluded.data",
+        [raw],
+    )
+    .map(|_| ())
+    .map_err(sql_err)
 }
 
-pub fn load() -> ProgressionSave {
-    let path = progression_path();
-    let raw = match std::fs::read_to_string(&path) {
-        Ok(s) => s,
-        Err(_) => return ProgressionSave::default(),
-    };
-    serde_json::from_str(&raw).unwrap_or_default()
+pub fn load() -> Result<ProgressionSave, String> {
+    let conn = db::connect()?;
+    let raw: Option<String> = conn
+        .query_row("SELECT data FROM progression WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .optional()
+        .map_err(sql_err)?;
+    match raw {
+        Some(raw) => {
+            serde_json::from_str(&raw).map_err(|e| format!("Invalid progression data: {e}"))
+        }
+        None => Ok(ProgressionSave::default()),
+    }
 }
 
-pub fn save(data: ProgressionSave) -> ProgressionSave {
-    if let Some(parent) = progression_path().parent() {
-        let _ = std::fs::create_dir_all(parent);
-    }
-    if let Ok(json) = serde_json::to_string_pretty(&data) {
-        let _ = std::fs::write(progression_path(), json);
-    }
-    data
+pub fn save(data: ProgressionSave) -> Result<ProgressionSave, String> {
+    save_in(&db::connect()?, &data)?;
+    Ok(data)
 }
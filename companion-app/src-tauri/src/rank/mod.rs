@@ -0,0 +1,409 @@
+//! Ranked standings for every queue (solo, flex, …) and the LP history log.
+
+pub mod store;
+
+use crate::lcu::LcuClient;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const SOLO_QUEUE: &str = "RANKED_SOLO_5x5";
+pub const FLEX_QUEUE: &str = "RANKED_FLEX_SR";
+
+const TIERS: &[&str] = &[
+    "IRON",
+    "BRONZE",
+    "SILVER",
+    "GOLD",
+    "PLATINUM",
+    "EMERALD",
+    "DIAMOND",
+    "MASTER",
+    "GRANDMASTER",
+    "CHALLENGER",
+];
+const DIVISIONS: &[&str] = &["IV", "III", "II", "I"];
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRank {
+    pub queue_type: String,
+    #[serde(default)]
+    pub tier: String,
+    #[serde(default)]
+    pub division: String,
+    #[serde(default)]
+    pub league_points: i32,
+    #[serde(default)]
+    pub wins: u32,
+    #[serde(default)]
+    pub losses: u32,
+    #[serde(default)]
+    pub is_provisional: bool,
+}
+
+impl QueueRank {
+    pub fn is_ranked(&self) -> bool {
+        tier_index(&self.tier).is_some()
+    }
+
+    /// `Gold II`, `Master` or empty when unranked.
+    pub fn display(&self) -> String {
+        format_rank_tier(&self.tier, &self.division)
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// LP on one continuous ladder (Iron IV 0 LP = 0, 100 per division; Master+ share a band).
+    pub fn ladder_points(&self) -> Option<i32> {
+        let tier = tier_index(&self.tier)? as i32;
+        let master = tier_index("MASTER").unwrap_or(7) as i32;
+        if tier >= master {
+            return Some(master * 400 + self.league_points);
+        }
+        let division = DIVISIONS
+            .iter()
+            .position(|d| d.eq_ignore_ascii_case(&self.division))
+            .unwrap_or(0) as i32;
+        Some(tier * 400 + division * 100 + self.league_points)
+    }
+}
+
+fn tier_index(tier: &str) -> Option<usize> {
+    TIERS.iter().position(|t| t.eq_ignore_ascii_case(tier))
+}
+
+pub(crate) fn format_rank_tier(tier: &str, division: &str) -> String {
+    if tier.is_empty() || tier.eq_ignore_ascii_case("UNRANKED") {
+        return String::new();
+    }
+    let tier_lower = tier.to_ascii_lowercase();
+    let tier_fmt = tier_lower
+        .chars()
+        .next()
+        .map(|c| c.to_uppercase().collect::<String>())
+        .unwrap_or_default()
+        + &tier_lower.chars().skip(1).collect::<String>();
+    if division.is_empty() || division.eq_ignore_ascii_case("NA") {
+        tier_fmt
+    } else {
+        format!("{tier_fmt} {division}")
+    }
+}
+
+/// Queues from `/lol-ranked/v1/ranked-overview/{summonerId}` (or a bare queue array).
+pub fn parse_ranked_queues(v: &Value) -> Vec<QueueRank> {
+    let queues = v
+        .get("queues")
+        .and_then(|q| q.as_array())
+        .or_else(|| v.as_array());
+    let Some(queues) = queues else {
+        return Vec::new();
+    };
+    queues
+        .iter()
+        .filter_map(|q| {
+            let str_of = |key: &str| q.get(key).and_then(|x| x.as_str()).unwrap_or("");
+            let queue_type = str_of("queueType");
+            if queue_type.is_empty() {
+                return None;
+            }
+            let division = q
+                .get("division")
+                .or_else(|| q.get("rank"))
+                .and_then(|x| x.as_str())
+                .unwrap_or("");
+            let count = |key: &str| q.get(key).and_then(|x| x.as_u64()).unwrap_or(0) as u32;
+            Some(QueueRank {
+                queue_type: queue_type.to_string(),
+                tier: str_of("tier").to_string(),
+                division: division.to_string(),
+                league_points: q.get("leaguePoints").and_then(|x| x.as_i64()).unwrap_or(0) as i32,
+                wins: count("wins"),
+                losses: count("losses"),
+                is_provisional: q
+                    .get("isProvisional")
+                    .and_then(|x| x.as_bool())
+                    .unwrap_or(false),
+            })
+        })
+        .collect()
+}
+
+pub fn fetch_ranked_queues(client: &LcuClient, summoner_id: u64) -> Result<Vec<QueueRank>, String> {
+    let raw = client.get(&format!("/lol-ranked/v1/ranked-overview/{summoner_id}"))?;
+    let v: Value =
+        serde_json::from_str(&raw).map_err(|e| format!("Invalid ranked overview JSON: {e}"))?;
+    Ok(parse_ranked_queues(&v))
+}
+
+/// Ranked queue a game counts for (`queueType` from EOG, else the numeric queue id).
+pub fn queue_type_of_game(queue_type: &str, queue_id: Option<u32>) -> Option<String> {
+    if queue_type.starts_with("RANKED_") {
+        return Some(queue_type.to_string());
+    }
+    match queue_id? {
+        420 => Some(SOLO_QUEUE.into()),
+        440 => Some(FLEX_QUEUE.into()),
+        _ => None,
+    }
+}
+
+/// Rank shown on a game: its own queue, else solo, else flex, else any ranked queue.
+pub fn rank_for_game<'a>(
+    queues: &'a [QueueRank],
+    game_queue: Option<&str>,
+) -> Option<&'a QueueRank> {
+    let ranked = |qt: &str| queues.iter().find(|q| q.queue_type == qt && q.is_ranked());
+    game_queue
+        .and_then(ranked)
+        .or_else(|| ranked(SOLO_QUEUE))
+        .or_else(|| ranked(FLEX_QUEUE))
+        .or_else(|| queues.iter().find(|q| q.is_ranked()))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LpChangeKind {
+    /// First snapshot of the queue.
+    Initial,
+    Gain,
+    Loss,
+    /// Games played changed but LP did not (e.g. demotion shield, remake).
+    Neutral,
+    Promotion,
+    Demotion,
+    /// Season / split reset: games played dropped or the rank was removed.
+    Reset,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LpHistoryEntry {
+    pub taken_at_ms: i64,
+    #[serde(flatten)]
+    pub rank: QueueRank,
+    /// `connect` or `postgame`.
+    pub source: String,
+    #[serde(default)]
+    pub game_id: Option<u64>,
+    pub change: LpChangeKind,
+    /// Ladder LP difference to the previous entry (not set across resets).
+    #[serde(default)]
+    pub lp_delta: Option<i32>,
+}
+
+/// How `next` differs from the previous entry of its queue; `None` when nothing changed.
+pub fn classify_change(prev: Option<&QueueRank>, next: &QueueRank) -> Option<LpChangeKind> {
+    let Some(prev) = prev else {
+        return next.is_ranked().then_some(LpChangeKind::Initial);
+    };
+    if prev == next {
+        return None;
+    }
+    if next.games() < prev.games() || (prev.is_ranked() && !next.is_ranked()) {
+        return Some(LpChangeKind::Reset);
+    }
+    let (Some(before), Some(after)) = (prev.ladder_points(), next.ladder_points()) else {
+        return next.is_ranked().then_some(LpChangeKind::Initial);
+    };
+    let same_division = prev.tier.eq_ignore_ascii_case(&next.tier)
+        && prev.division.eq_ignore_ascii_case(&next.division);
+    Some(match (same_division, after.cmp(&before)) {
+        (false, std::cmp::Ordering::Less) => LpChangeKind::Demotion,
+        (false, _) => LpChangeKind::Promotion,
+        (true, std::cmp::Ordering::Greater) => LpChangeKind::Gain,
+        (true, std::cmp::Ordering::Less) => LpChangeKind::Loss,
+        (true, std::cmp::Ordering::Equal) => LpChangeKind::Neutral,
+    })
+}
+
+/// New history entries for `queues` compared with the latest entry of each queue.
+pub fn diff_snapshot(
+    history: &[LpHistoryEntry],
+    queues: &[QueueRank],
+    taken_at_ms: i64,
+    source: &str,
+    game_id: Option<u64>,
+) -> Vec<LpHistoryEntry> {
+    queues
+        .iter()
+        .filter_map(|q| {
+            let prev = history
+                .iter()
+                .rev()
+                .find(|e| e.rank.queue_type == q.queue_type)
+                .map(|e| &e.rank);
+            let change = classify_change(prev, q)?;
+            let lp_delta = match (&change, prev.and_then(QueueRank::ladder_points)) {
+                (LpChangeKind::Initial | LpChangeKind::Reset, _) | (_, None) => None,
+                (_, Some(before)) => q.ladder_points().map(|after| after - before),
+            };
+            Some(LpHistoryEntry {
+                taken_at_ms,
+                rank: q.clone(),
+                source: source.to_string(),
+                game_id,
+                change,
+                lp_delta,
+            })
+        })
+        .collect()
+}
+
+/// Fetch every queue and append changed ones to the LP history; returns the new entries.
+pub fn record_snapshot(
+    client: &LcuClient,
+    summoner_id: u64,
+    source: &str,
+    game_id: Option<u64>,
+) -> Result<Vec<LpHistoryEntry>, String> {
+    let queues = fetch_ranked_queues(client, summoner_id)?;
+    let history = store::load_history()?;
+    let now = crate::checklist::evaluator::now_millis();
+    let added = diff_snapshot(&history, &queues, now, source, game_id);
+    if !added.is_empty() {
+        store::append(&added)?;
+    }
+    Ok(added)
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LpPoint {
+    pub taken_at_ms: i64,
+    /// `None` while unranked (gap in the chart).
+    pub ladder_points: Option<i32>,
+    pub rank: String,
+    pub league_points: i32,
+    pub change: LpChangeKind,
+    pub lp_delta: Option<i32>,
+    pub game_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LpSeries {
+    pub queue_type: String,
+    pub points: Vec<LpPoint>,
+}
+
+/// One chart series per queue (solo first, then flex, then others), oldest point first.
+pub fn build_series(history: &[LpHistoryEntry], queue_type: Option<&str>) -> Vec<LpSeries> {
+    let mut series: Vec<LpSeries> = Vec::new();
+    let mut sorted: Vec<&LpHistoryEntry> = history
+        .iter()
+        .filter(|e| queue_type.is_none_or(|qt| e.rank.queue_type == qt))
+        .collect();
+    sorted.sort_by_key(|e| e.taken_at_ms);
+    for e in sorted {
+        let point = LpPoint {
+            taken_at_ms: e.taken_at_ms,
+            ladder_points: e.rank.ladder_points(),
+            rank: e.rank.display(),
+            league_points: e.rank.league_points,
+            change: e.change.clone(),
+            lp_delta: e.lp_delta,
+            game_id: e.game_id,
+        };
+        match series
+            .iter_mut()
+            .find(|s| s.queue_type == e.rank.queue_type)
+        {
+            Some(s) => s.points.push(point),
+            None => series.push(LpSeries {
+                queue_type: e.rank.queue_type.clone(),
+                points: vec![point],
+            }),
+        }
+    }
+    let order = |qt: &str| match qt {
+        SOLO_QUEUE => 0,
+        FLEX_QUEUE => 1,
+        _ => 2,
+    };
+    series.sort_by(|a, b| {
+        order(&a.queue_type)
+            .cmp(&order(&b.queue_type))
+            .then_with(|| a.queue_type.cmp(&b.queue_type))
+    });
+    series
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rank(queue: &str, tier: &str, division: &str, lp: i32, wins: u32, losses: u32) -> QueueRank {
+        QueueRank {
+            queue_type: queue.into(),
+            tier: tier.into(),
+            division: division.into(),
+            league_points: lp,
+            wins,
+            losses,
+            is_provisional: false,
+        }
+    }
+
+    #[test]
+    fn parses_every_queue() {
+        let v: Value = serde_json::from_str(
+            r#"{ "queues": [
+                { "queueType": "RANKED_SOLO_5x5", "tier": "GOLD", "division": "II", "leaguePoints": 45, "wins": 20, "losses": 18 },
+                { "queueType": "RANKED_FLEX_SR", "tier": "SILVER", "rank": "I", "leaguePoints": 80 },
+                { "queueType": "RANKED_TFT", "tier": "NONE", "division": "NA" }
+            ] }"#,
+        )
+        .unwrap();
+        let queues = parse_ranked_queues(&v);
+        assert_eq!(queues.len(), 3);
+        assert_eq!(queues[1].display(), "Silver I");
+        assert!(!queues[2].is_ranked());
+        let flex = rank_for_game(&queues, Some(FLEX_QUEUE)).unwrap();
+        assert_eq!(flex.league_points, 80);
+        assert_eq!(rank_for_game(&queues, None).unwrap().queue_type, SOLO_QUEUE);
+        assert_eq!(queues[0].ladder_points(), Some(3 * 400 + 2 * 100 + 45));
+    }
+
+    #[test]
+    fn logs_promotions_losses_and_resets() {
+        let mut history = Vec::new();
+        let steps = [
+            rank(SOLO_QUEUE, "GOLD", "I", 90, 10, 10),
+            rank(SOLO_QUEUE, "GOLD", "I", 90, 10, 10),
+            rank(SOLO_QUEUE, "PLATINUM", "IV", 5, 11, 10),
+            rank(SOLO_QUEUE, "PLATINUM", "IV", 0, 11, 11),
+            rank(SOLO_QUEUE, "GOLD", "I", 75, 11, 12),
+            rank(SOLO_QUEUE, "", "", 0, 0, 0),
+        ];
+        for (t, step) in steps.iter().enumerate() {
+            let added = diff_snapshot(
+                &history,
+                std::slice::from_ref(step),
+                t as i64,
+                "postgame",
+                None,
+            );
+            history.extend(added);
+        }
+        let kinds: Vec<_> = history.iter().map(|e| e.change.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LpChangeKind::Initial,
+                LpChangeKind::Promotion,
+                LpChangeKind::Loss,
+                LpChangeKind::Demotion,
+                LpChangeKind::Reset,
+            ]
+        );
+        assert_eq!(history[1].lp_delta, Some(15));
+
+        let series = build_series(&history, None);
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].points.len(), 5);
+        assert_eq!(series[0].points[4].ladder_points, None);
+    }
+}
@@ -0,0 +1,46 @@
+//! LP history log in the SQLite database.
+
+use super::LpHistoryEntry;
+use crate::db::{self, sql_err};
+use rusqlite::{params, Connection};
+
+pub(crate) fn insert_in(conn: &Connection, entry: &LpHistoryEntry) -> Result<(), String> {
+    let data = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    conn.execute(
+        "INSERT INTO lp_history (taken_at_ms, queue_type, data) VALUES (?1, ?2, ?3)",
+        params![entry.taken_at_ms, entry.rank.queue_type, data],
+    )
+    .map(|_| ())
+    .map_err(sql_err)
+}
+
+/// Oldest first. Rows this version cannot decode are skipped but left in the database
+/// (counted in the storage status).
+pub(crate) fn load_in(conn: &Connection) -> Result<Vec<LpHistoryEntry>, String> {
+    let mut stmt = conn
+        .prepare("SELECT data FROM lp_history ORDER BY taken_at_ms, id")
+        .map_err(sql_err)?;
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(sql_err)?;
+    let mut entries = Vec::new();
+    for raw in rows {
+        if let Ok(entry) = serde_json::from_str(&raw.map_err(sql_err)?) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+pub fn load_history() -> Result<Vec<LpHistoryEntry>, String> {
+    load_in(&db::connect()?)
+}
+
+pub fn append(entries: &[LpHistoryEntry]) -> Result<(), String> {
+    let mut conn = db::connect()?;
+    let tx = conn.transaction().map_err(sql_err)?;
+    for entry in entries {
+        insert_in(&tx, entry)?;
+    }
+    tx.commit().map_err(sql_err)
+}
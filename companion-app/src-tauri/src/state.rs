@@ -1,8 +1,10 @@
 //! Shared application state for LCU export.
 
+use crate::checklist::focus::FocusSuggestion;
+use crate::checklist::rescore::Reevaluation;
 use crate::checklist::SavedChecklist;
 use crate::lcu::{ItemSetData, RunePageData};
-use crate::live_client::LiveCsSnapshot;
+use crate::live_client::{LiveCsSnapshot, LiveTimeline};
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
 
@@ -57,9 +59,20 @@ pub struct AppState {
     pub connected: Mutex<bool>,
     pub gameflow_phase: Mutex<String>,
     pub champion_id: Mutex<Option<u32>>,
+    /// Position assigned in champ select (`TOP`, `JUNGLE`, …), kept until the next one.
+    pub assigned_position: Mutex<Option<String>>,
+    /// Game client version captured when the game starts.
+    pub game_version: Mutex<String>,
     pub pending_build: Mutex<Option<BuildPayload>>,
     pub live_cs_snapshot: Mutex<LiveCsSnapshot>,
+    pub live_timeline: Mutex<LiveTimeline>,
     pub checklist_draft: Mutex<Option<SavedChecklist>>,
+    /// LCU id of the game the live snapshot and draft belong to.
+    pub live_game_id: Mutex<Option<u64>>,
+    /// Focus points picked in champ select, copied onto the game's checklist.
+    pub focus: Mutex<Vec<FocusSuggestion>>,
+    /// Last history re-evaluation preview, written as is when the user applies it.
+    pub pending_reevaluation: Mutex<Option<Reevaluation>>,
 }
 
 impl AppState {
@@ -68,9 +81,15 @@ impl AppState {
             connected: Mutex::new(false),
             gameflow_phase: Mutex::new("None".into()),
             champion_id: Mutex::new(None),
+            assigned_position: Mutex::new(None),
+            game_version: Mutex::new(String::new()),
             pending_build: Mutex::new(None),
             live_cs_snapshot: Mutex::new(LiveCsSnapshot::default()),
+            live_timeline: Mutex::new(LiveTimeline::default()),
             checklist_draft: Mutex::new(None),
+            live_game_id: Mutex::new(None),
+            focus: Mutex::new(Vec::new()),
+            pending_reevaluation: Mutex::new(None),
         }
     }
 
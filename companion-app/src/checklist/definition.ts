@@ -1,4 +1,4 @@
-import type { ChecklistItem, CheckResultKind } from "../types/checklist";
+import type { ChecklistItem, CheckResultKind, ScoreWeights } from "../types/checklist";
 
 export const CHECKLIST_SECTION_ORDER = [
   "farm",
@@ -15,8 +15,10 @@ export const CHECKLIST_ITEM_ORDER = [
   "pink_wards",
   "wards_placed",
   "vision_denied",
+  "vision_score",
   "survival",
   "team_objectives",
+  "objective_participation",
   "minimap",
   "ping_missing",
   "wave_management",
@@ -30,8 +32,10 @@ export const ITEM_SECTIONS: Record<string, string> = {
   pink_wards: "warding",
   wards_placed: "warding",
   vision_denied: "warding",
+  vision_score: "warding",
   survival: "survival",
   team_objectives: "objectives",
+  objective_participation: "objectives",
   minimap: "manual",
   ping_missing: "manual",
   wave_management: "manual",
@@ -47,10 +51,15 @@ export const MEASURABLE_IDS = new Set<string>([
   "vision_denied",
   "survival",
   "team_objectives",
+  "vision_score",
+  "objective_participation",
 ]);
 
+/** Items defined in the checklist rules (`customItems`). */
+export const CUSTOM_ITEM_PREFIX = "custom:";
+
 export function isMeasurableItem(id: string): boolean {
-  return MEASURABLE_IDS.has(id);
+  return MEASURABLE_IDS.has(id) || id.startsWith(CUSTOM_ITEM_PREFIX);
 }
 
 export function blankItem(id: string): ChecklistItem {
@@ -68,31 +77,53 @@ export function createBlankChecklistItems(): ChecklistItem[] {
 
 export function normalizeChecklistItems(saved: ChecklistItem[]): ChecklistItem[] {
   const byId = new Map(saved.map((i) => [i.id, i]));
-  return CHECKLIST_ITEM_ORDER.map((id) => {
+  const builtIn = CHECKLIST_ITEM_ORDER.map((id) => {
     const existing = byId.get(id);
     if (existing) return { ...existing };
     return blankItem(id);
   });
+  const custom = saved
+    .filter((i) => i.id.startsWith(CUSTOM_ITEM_PREFIX))
+    .map((i) => ({ ...i }));
+  return [...builtIn, ...custom];
 }
 
-export function recalcChecklistScore(items: ChecklistItem[]): {
+/**
+ * Mirrors `recalculate_score`; pass the checklist's `weights` (equal, half credit by default)
+ * and its `unscored` flag.
+ */
+export function recalcChecklistScore(
+  items: ChecklistItem[],
+  weights?: ScoreWeights | null,
+  unscored = false,
+): {
   score: number;
   measuredCount: number;
   checkedCount: number;
 } {
-  const measured = items.filter((i) => isMeasurableItem(i.id));
-  const measuredCount = measured.length;
   const effective = (item: ChecklistItem): CheckResultKind => {
+    if (isMeasurableItem(item.id) && (unscored || item.kind === "unmeasurable")) {
+      // Unscored game, or not applicable to the role / mode.
+      return "unmeasurable";
+    }
     if (isMeasurableItem(item.id)) {
       return item.userKind ?? item.kind;
     }
     return item.manualChecked ? "checked" : "unmeasurable";
   };
+  const measured = items.filter(
+    (i) => isMeasurableItem(i.id) && effective(i) !== "unmeasurable",
+  );
+  const measuredCount = measured.length;
   const checkedCount = measured.filter((i) => effective(i) === "checked").length;
-  const partialCount = measured.filter((i) => effective(i) === "partial").length;
-  const score =
-    measuredCount === 0
-      ? 0
-      : ((checkedCount + partialCount * 0.5) / measuredCount) * 100;
+  const partialCredit = weights?.partialCredit ?? 0.5;
+  const weightOf = (id: string) => weights?.items?.[id] ?? 1;
+  const totalWeight = measured.reduce((sum, i) => sum + weightOf(i.id), 0);
+  const earned = measured.reduce((sum, i) => {
+    const kind = effective(i);
+    const credit = kind === "checked" ? 1 : kind === "partial" ? partialCredit : 0;
+    return sum + weightOf(i.id) * credit;
+  }, 0);
+  const score = totalWeight <= 0 ? 0 : (earned / totalWeight) * 100;
   return { score, measuredCount, checkedCount };
 }
@@ -0,0 +1,32 @@
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+
+export type ExportFormat = "csv" | "jsonl" | "markdown" | "html";
+
+/** Games to export: the listed ids, else everything in the date range. */
+export interface ExportSelection {
+  ids?: string[];
+  fromMs?: number;
+  toMs?: number;
+}
+
+const EXTENSIONS: Record<ExportFormat, string> = {
+  csv: "csv",
+  jsonl: "jsonl",
+  markdown: "md",
+  html: "html",
+};
+
+/** Ask for a destination, then write the export; `null` when the dialog is cancelled. */
+export async function exportChecklistHistory(
+  format: ExportFormat,
+  selection?: ExportSelection,
+): Promise<number | null> {
+  const extension = EXTENSIONS[format];
+  const path = await save({
+    defaultPath: `lelanation-checklists.${extension}`,
+    filters: [{ name: format.toUpperCase(), extensions: [extension] }],
+  });
+  if (!path) return null;
+  return invoke<number>("export_checklist_history", { format, path, selection });
+}
@@ -0,0 +1,13 @@
+import { invoke } from "@tauri-apps/api/core";
+import { open } from "@tauri-apps/plugin-dialog";
+import type { ImportSummary } from "../types/checklist";
+
+/** Pick an exported history (JSON or JSON Lines) and merge it; `null` when cancelled. */
+export async function importChecklistHistory(): Promise<ImportSummary | null> {
+  const path = await open({
+    multiple: false,
+    filters: [{ name: "Checklist history", extensions: ["json", "jsonl"] }],
+  });
+  if (typeof path !== "string") return null;
+  return invoke<ImportSummary>("import_checklist_history", { path });
+}
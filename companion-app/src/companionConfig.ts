@@ -3,4 +3,35 @@ export interface CompanionConfig {
   leagueInstallPath: string | null;
   onboardingComplete: boolean;
   shareRankedDuoStats: boolean;
+  /** Game minutes at which live CS is recorded (empty = 5 and 10). */
+  csMilestoneMinutes?: number[];
+  /** What to do with remakes, early surrenders and non-Rift modes. */
+  gameScoring?: GameScoringSettings;
+  /** Days raw game archives are kept (null = forever). */
+  archiveRetentionDays?: number | null;
+  /** Play session grouping and tilt warnings. */
+  sessions?: SessionSettings;
+}
+
+/** `evaluate` uses the mode's profile, `unscored` saves without a score, `skip` does not save. */
+export type ScoringAction = "evaluate" | "unscored" | "skip";
+
+export interface GameScoringSettings {
+  remake: ScoringAction;
+  earlySurrender: ScoringAction;
+  leaver: ScoringAction;
+  aram: ScoringAction;
+  arena: ScoringAction;
+  otherModes: ScoringAction;
+}
+
+export interface SessionSettings {
+  /** Minutes without a game before the next one starts a new session. */
+  gapMinutes: number;
+  tiltWarnings: boolean;
+  lossStreak: number;
+  scoreDrop: number;
+  deathsRise: number;
+  /** Signals at which the warning recommends to stop playing (0 = never). */
+  stopAfterSignals: number;
 }
@@ -243,6 +243,8 @@ const messages: Record<string, Record<string, string>> = {
     "checklist.items.vision_denied": "Vision ennemie détruite",
     "checklist.items.survival": "Morts",
     "checklist.items.team_objectives": "Objectifs d'équipe (dragons:barons)",
+    "checklist.items.vision_score": "Score de vision / minute",
+    "checklist.items.objective_participation": "Participation aux objectifs (dragons, barons)",
     "checklist.items.minimap": "Je vérifie la minimap régulièrement",
     "checklist.items.ping_missing": "Je ping quand l'adversaire est absent",
     "checklist.items.wave_management": "Gestion de wave / back timing",
@@ -256,6 +258,8 @@ const messages: Record<string, Record<string, string>> = {
     "checklist.detail.vision_denied": "{value} ward(s)",
     "checklist.detail.survival": "{value} mort(s)",
     "checklist.detail.team_objectives": "Dragons:Barons {value}",
+    "checklist.detail.vision_score": "{value}/min",
+    "checklist.detail.objective_participation": "{value} objectif(s)",
     "checklist.notifySaved": "Checklist auto-enregistrée — voir l'onglet Checklist.",
     "shortcuts.title": "Commandes et raccourcis",
     "shortcuts.hint": "Ces raccourcis fonctionnent dans les pages du site affichées dans l'application.",
@@ -517,6 +521,8 @@ const messages: Record<string, Record<string, string>> = {
     "checklist.items.vision_denied": "Enemy vision denied",
     "checklist.items.survival": "Deaths",
     "checklist.items.team_objectives": "Team objectives (dragons:barons)",
+    "checklist.items.vision_score": "Vision score per minute",
+    "checklist.items.objective_participation": "Objective participation (dragons, barons)",
     "checklist.items.minimap": "I check the minimap regularly",
     "checklist.items.ping_missing": "I ping when my opponent is missing",
     "checklist.items.wave_management": "Wave management / back timing",
@@ -530,6 +536,8 @@ const messages: Record<string, Record<string, string>> = {
     "checklist.detail.vision_denied": "{value} ward(s)",
     "checklist.detail.survival": "{value} death(s)",
     "checklist.detail.team_objectives": "Dragons:Barons {value}",
+    "checklist.detail.vision_score": "{value}/min",
+    "checklist.detail.objective_participation": "{value} objective(s)",
     "checklist.notifySaved": "Checklist auto-saved — see the Checklist tab.",
     "shortcuts.title": "Commands and shortcuts",
     "shortcuts.hint": "These shortcuts work in the site pages displayed inside the app.",
@@ -6,11 +6,36 @@ export interface ChecklistItem {
   detail?: string | null;
   userKind?: CheckResultKind | null;
   manualChecked?: boolean;
+  /** Display name of `custom:*` items. */
+  label?: string | null;
+}
+
+export interface PostGameParticipant {
+  summonerId?: number | null;
+  riotId: string;
+  championId: number;
+  teamId?: number | null;
+  position: string;
+  isLocal: boolean;
+  win: boolean;
+  kills: number;
+  deaths: number;
+  assists: number;
+  cs: number;
+  goldEarned: number;
+  damageToChampions: number;
+  damageTaken: number;
+  visionScore: number;
+  wardsPlaced: number;
+  wardsKilled: number;
+  items: number[];
 }
 
 export interface PostGameStats {
   gameId?: number | null;
   gameDurationSeconds: number;
+  /** CS per game minute, keyed by minute (e.g. `{ "5": 44, "10": 90 }`). */
+  csMilestones?: Record<string, number>;
   csAt5?: number | null;
   csAt10?: number | null;
   csTotal: number;
@@ -37,6 +62,49 @@ export interface PostGameStats {
   allyTeamAvgElo?: number | null;
   enemyTeamAvgElo?: number | null;
   gameAvgElo?: number | null;
+  participants?: PostGameParticipant[];
+  /** Local player's per-minute frames from the LCU game timeline. */
+  minuteFrames?: MinuteFrame[];
+  endedInEarlySurrender?: boolean;
+  endedInSurrender?: boolean;
+  leaver?: boolean;
+  /** `TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM` or `UTILITY`; picks the checklist rule set. */
+  position?: string;
+  visionScore?: number;
+  jungleCs?: number;
+  epicMonsterKills?: number;
+}
+
+export interface MinuteFrame {
+  minute: number;
+  cs: number;
+  gold: number;
+  xp: number;
+  level: number;
+}
+
+export type TimelineEventKind =
+  | "kill"
+  | "death"
+  | "assist"
+  | "firstBlood"
+  | "dragon"
+  | "herald"
+  | "baron"
+  | "turret"
+  | "inhibitor";
+
+export interface TimelineEvent {
+  eventId: number;
+  gameTimeSeconds: number;
+  kind: TimelineEventKind;
+  killer: string;
+  victim: string;
+  assisters: string[];
+  dragonType?: string | null;
+  stolen: boolean;
+  structure?: string | null;
+  ally?: boolean | null;
 }
 
 export interface SavedChecklist {
@@ -49,4 +117,300 @@ export interface SavedChecklist {
   checkedCount: number;
   stats: PostGameStats;
   notes?: string;
+  timeline?: TimelineEvent[];
+  gameClass?: GameClass;
+  /** Saved without a score (remake, leaver, …). */
+  unscored?: boolean;
+  /** `version` of the checklist rules used for the items. */
+  rulesetVersion?: string;
+  /** Weights the score was computed with. */
+  weights?: ScoreWeights;
+  tags?: string[];
+  /** Items suggested as pre-game focus for this game. */
+  focus?: string[];
+  /** Evaluator release the items come from (0: before versioning). */
+  evaluatorVersion?: number;
+}
+
+export type GameMode = "summonersRift" | "aram" | "arena" | "other";
+export type GameEnding = "normal" | "remake" | "earlySurrender" | "leaver";
+
+export interface GameClass {
+  mode: GameMode;
+  ending: GameEnding;
+}
+
+/** Result of `rebuild_checklists_from_archive`. */
+export interface RebuildSummary {
+  archived: number;
+  updated: number;
+  added: number;
+  skipped: number;
+  failed: number;
+  /** Entries edited while the rebuild ran; left as they were. */
+  stale: number;
+}
+
+/** Higher is better from `partial` / `checked` up (deaths: at most). */
+export interface Threshold {
+  checked: number;
+  partial: number;
+}
+
+export interface Thresholds {
+  csAt5: Threshold;
+  csAt10: Threshold;
+  csPerMin: Threshold;
+  pinkWards: Threshold;
+  wardsPerMin: Threshold;
+  visionDenied: Threshold;
+  deaths: Threshold;
+  teamObjectives: Threshold;
+  visionScorePerMin: Threshold;
+  objectiveParticipation: Threshold;
+}
+
+/** Item id → weight (default 1) and the share of it earned when partial. */
+export interface ScoreWeights {
+  items?: Record<string, number>;
+  partialCredit?: number;
+}
+
+/** Item scored from an expression such as `kills + assists >= 10`; id is `custom:<id>`. */
+export interface CustomItem {
+  id: string;
+  label: string;
+  expression: string;
+  /** Without thresholds a non-zero result is checked and zero failed. */
+  threshold?: Threshold | null;
+  lowerIsBetter?: boolean;
+}
+
+/** `get_checklist_rules` / `set_checklist_rules`; overrides are partial `Thresholds`. */
+export interface ChecklistRules {
+  version: string;
+  thresholds: Thresholds;
+  modes?: Record<string, Partial<Thresholds>>;
+  roles?: Record<string, Partial<Thresholds>>;
+  ranks?: Record<string, Partial<Thresholds>>;
+  customItems?: CustomItem[];
+  weights?: ScoreWeights;
+}
+
+/** `get_storage_status`: database schema and the outcome of the one-time JSON import. */
+export interface StorageStatus {
+  schemaVersion: number;
+  checklistCount: number;
+  /** Checklists and LP history entries this version cannot read (kept in the database). */
+  undecodableCount: number;
+  importError?: string | null;
+}
+
+export type HistorySort = "savedAt" | "score";
+
+/** `query_checklist_history` filters; unset fields match everything. */
+export interface HistoryQuery {
+  fromMs?: number;
+  toMs?: number;
+  championId?: number;
+  queueId?: number;
+  /** Team position (`TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM`, `UTILITY`). */
+  position?: string;
+  win?: boolean;
+  rankedOnly?: boolean;
+  /** Leave out unscored games (remakes and modes the scoring settings exclude). */
+  scoredOnly?: boolean;
+  minScore?: number;
+  maxScore?: number;
+  /** Case-insensitive search in the notes. */
+  text?: string;
+  /** Entries must carry all of these tags. */
+  tags?: string[];
+  sort?: HistorySort;
+  ascending?: boolean;
+  offset?: number;
+  /** Default 50, at most 500. */
+  limit?: number;
+}
+
+export interface HistoryPage {
+  entries: SavedChecklist[];
+  total: number;
+  offset: number;
+  limit: number;
+}
+
+/** Averages over a set of games; LP change only counts games where it is known. */
+export interface MetricSummary {
+  games: number;
+  avgScore: number;
+  avgCsPerMin: number;
+  avgDeaths: number;
+  avgWardsPerMin: number;
+  winRate: number;
+  lpChangeTotal: number;
+  avgLpChange?: number | null;
+}
+
+export interface RollingPoint {
+  savedAtMs: number;
+  gameId?: number | null;
+  score: number;
+  avgScore: number;
+  avgCsPerMin: number;
+  avgDeaths: number;
+  avgWardsPerMin: number;
+  winRate: number;
+}
+
+export interface RollingSeries {
+  window: number;
+  latest: MetricSummary;
+  /** Oldest first. */
+  points: RollingPoint[];
+}
+
+/** Champion id or queue type, with the same metrics. */
+export interface GroupSummary extends MetricSummary {
+  key: string;
+}
+
+export interface ItemPassRate {
+  id: string;
+  measured: number;
+  checked: number;
+  partial: number;
+  failed: number;
+  passRate: number;
+}
+
+/** `get_checklist_analytics` (scored games only). */
+export interface ChecklistAnalytics {
+  overall: MetricSummary;
+  rolling: RollingSeries[];
+  byChampion: GroupSummary[];
+  byQueue: GroupSummary[];
+  items: ItemPassRate[];
+}
+
+export interface ImportSummary {
+  added: number;
+  updated: number;
+  /** Already stored with every user edit, invalid, or matching an unreadable stored entry (see `problems`). */
+  skipped: number;
+  problems: string[];
+}
+
+export type GoalMode = "window" | "streak";
+export type GoalStatus = "active" | "completed" | "failed";
+
+export interface GoalGame {
+  checklistId: string;
+  gameId: number | null;
+  savedAtMs: number;
+  passed: boolean;
+}
+
+/** `target` passing games out of the next `window` (window) or in a row (streak). */
+export interface Goal {
+  id: string;
+  label: string;
+  /** Checklist item that must be checked, or… */
+  itemId?: string | null;
+  /** …an expression over the game's stats (`cs_at_10 >= 80`). */
+  expression?: string | null;
+  mode: GoalMode;
+  target: number;
+  window: number;
+  createdAtMs: number;
+  status: GoalStatus;
+  completedAtMs: number | null;
+  bestStreak: number;
+  history: GoalGame[];
+}
+
+/** Payload of `goal:progress` and `goal:completed`. */
+export interface GoalProgress {
+  goalId: string;
+  label: string;
+  mode: GoalMode;
+  status: GoalStatus;
+  passed: boolean;
+  /** Passing games so far (window) or the current streak. */
+  progress: number;
+  target: number;
+  games: number;
+  window: number;
+}
+
+export type FocusScope = "championRole" | "champion" | "role" | "all";
+
+/** Payload of `checklist:focus`: weakest items of the last games. */
+export interface FocusSuggestion {
+  id: string;
+  label: string | null;
+  passRate: number;
+  measured: number;
+  scope: FocusScope;
+}
+
+/** Payload of `checklist:focus-review`. */
+export interface FocusReview {
+  id: string;
+  kind: CheckResultKind;
+  previousPassRate: number;
+  improved: boolean;
+}
+
+export interface PlaySession {
+  startMs: number;
+  endMs: number;
+  games: number;
+  wins: number;
+  losses: number;
+  lpChange: number;
+  avgScore: number;
+  /** Score change per game over the session. */
+  scoreTrend: number;
+  avgDeaths: number;
+  checklistIds: string[];
+}
+
+export type TiltSignal = "lossStreak" | "fallingScore" | "risingDeaths";
+
+/** Payload of `session:tilt-warning`. */
+export interface TiltWarning {
+  sessionStartMs: number;
+  games: number;
+  signals: TiltSignal[];
+  lossStreak: number;
+  scoreDrop: number | null;
+  deathsRise: number | null;
+  stopPlaying: boolean;
+}
+
+export interface ScoreChange {
+  id: string;
+  savedAtMs: number;
+  gameId: number | null;
+  oldScore: number;
+  newScore: number;
+  changedItems: string[];
+}
+
+/** Result of `reevaluate_checklist_history` (a preview unless `applied`). */
+export interface RescorePreview {
+  total: number;
+  /** Entries evaluated with another evaluator or rules version. */
+  outdated: number;
+  changes: ScoreChange[];
+  avgScoreBefore: number;
+  avgScoreAfter: number;
+  evaluatorVersion: number;
+  rulesetVersion: string;
+  /** Only the current weights were applied; items were not re-evaluated. */
+  weightsOnly: boolean;
+  applied: boolean;
+  /** Entries edited or deleted since the preview, left as they are when applying. */
+  stale: number;
 }
@@ -0,0 +1,43 @@
+export interface QueueRank {
+  queueType: string;
+  tier: string;
+  division: string;
+  leaguePoints: number;
+  wins: number;
+  losses: number;
+  isProvisional: boolean;
+}
+
+export type LpChangeKind =
+  | "initial"
+  | "gain"
+  | "loss"
+  | "neutral"
+  | "promotion"
+  | "demotion"
+  | "reset";
+
+export interface LpHistoryEntry extends QueueRank {
+  takenAtMs: number;
+  /** `connect` or `postgame`. */
+  source: string;
+  gameId?: number | null;
+  change: LpChangeKind;
+  lpDelta?: number | null;
+}
+
+export interface LpPoint {
+  takenAtMs: number;
+  /** LP on one continuous ladder; `null` while unranked. */
+  ladderPoints: number | null;
+  rank: string;
+  leaguePoints: number;
+  change: LpChangeKind;
+  lpDelta: number | null;
+  gameId: number | null;
+}
+
+export interface LpSeries {
+  queueType: string;
+  points: LpPoint[];
+}